@@ -0,0 +1,79 @@
+//! Measures the throughput of finding the line breaks of large documents.
+//!
+//! Run with `cargo bench --bench breaks`.
+extern crate uax_14;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use uax_14::{BreakOpportunities, LineBreaks, Tailoring};
+
+// A tailoring without `ascii_classes`, to compare with the general path.
+struct General;
+
+impl Tailoring for General {}
+
+// How long every iterator is run on every document.
+const DURATION: Duration = Duration::from_millis(1000);
+
+// The size of every document, in bytes.
+const SIZE: usize = 1 << 20;
+
+// The throughput in MiB/s of running `f` on `input`.
+fn measure<F: Fn(&str) -> usize>(input: &str, f: F) -> f64 {
+    let start = Instant::now();
+    let mut runs = 0;
+    while start.elapsed() < DURATION {
+        black_box(f(black_box(input)));
+        runs += 1;
+    }
+    (runs * input.len()) as f64 / start.elapsed().as_secs_f64() / (1 << 20) as f64
+}
+
+// Repeat `paragraphs` until the document is `SIZE` bytes long.
+fn document(paragraphs: &[&str]) -> String {
+    let mut out = String::new();
+    for paragraph in paragraphs.iter().cycle() {
+        if out.len() + paragraph.len() > SIZE {
+            break;
+        }
+        out.push_str(paragraph);
+        out.push('\n');
+    }
+    out
+}
+
+fn main() {
+    let log = "2018-11-02T13:45:10.123Z INFO [worker-3] GET /api/v1/items?id=42 200 12ms \
+               user=alice@example.com bytes=1024";
+    let english = "The quick (\"brown\") fox can't jump 32.3 feet, right? Prices rose by \
+                   12.5% to $1,234.56 in 2018-2019, according to https://example.com/report.";
+    let german = "Ärger über Öl: Die Straße ist 3,5 km lang — «so» heißt es.";
+    let cjk = "木禾夫は、「日本語」のテキストです。中文的标点符号（括号）也要处理。";
+    let korean = "한국어 텍스트입니다. 그 사람은 일을 잘 한다!";
+    let arabic = "النص العربي مع الأرقام ١٢٣ والعلامات؟";
+    let emoji = "👍🏽 🇸🇪🇫🇮 👩‍👩‍👧 ✈️ done.";
+    let documents = [
+        ("log", document(&[log])),
+        ("english", document(&[english])),
+        ("cjk", document(&[cjk, korean])),
+        (
+            "mixed",
+            document(&[english, german, cjk, korean, arabic, emoji]),
+        ),
+    ];
+    println!(
+        "{:<10} {:>22} {:>22} {:>22}",
+        "document", "BreakOpportunities", "(without ASCII path)", "LineBreaks"
+    );
+    for &(name, ref input) in documents.iter() {
+        let opportunities = measure(input, |input| BreakOpportunities::new(input).count());
+        let general = measure(input, |input| {
+            BreakOpportunities::with_tailoring(input, General).count()
+        });
+        let line_breaks = measure(input, |input| LineBreaks::new(input).count());
+        println!(
+            "{:<10} {:>16.1} MiB/s {:>16.1} MiB/s {:>16.1} MiB/s",
+            name, opportunities, general, line_breaks
+        );
+    }
+}
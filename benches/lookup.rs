@@ -0,0 +1,70 @@
+//! Compares the table lookup of `line_break_property` with the `match` on
+//! ranges that it replaced.
+//!
+//! Run with `cargo bench --bench lookup`.
+extern crate uax_14;
+
+use std::char;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use uax_14::{line_break_property, LineBreakProperty};
+
+#[allow(unreachable_patterns)]
+fn line_break_property_match(n: char) -> LineBreakProperty {
+    // Automatically generated by `../build.rs`
+    include!(concat!(env!("OUT_DIR"), "/line_break_property_match"))
+}
+
+// How long every lookup function is run on every input.
+const DURATION: Duration = Duration::from_millis(500);
+
+// The average time in nanoseconds to look up a `char` of `input`.
+fn measure<F: Fn(char) -> LineBreakProperty>(input: &[char], f: F) -> f64 {
+    let start = Instant::now();
+    let mut runs = 0;
+    while start.elapsed() < DURATION {
+        for &c in input {
+            black_box(f(black_box(c)));
+        }
+        runs += 1;
+    }
+    start.elapsed().as_secs_f64() * 1e9 / (runs * input.len()) as f64
+}
+
+fn main() {
+    let all = (0..=char::MAX as u32)
+        .filter_map(char::from_u32)
+        .collect::<Vec<_>>();
+    for &c in &all {
+        assert_eq!(
+            line_break_property(c),
+            line_break_property_match(c),
+            "{:?}",
+            c
+        );
+    }
+
+    let inputs = [
+        (
+            "ascii",
+            "The quick (\"brown\") fox can't jump 32.3 feet, right?\n".repeat(20),
+        ),
+        (
+            "latin-1",
+            "Ærøskøbing ligger på Ærø, «ikke» på Fyn. Prisen er 12,50 €.\n".repeat(20),
+        ),
+        (
+            "cjk",
+            "木禾夫は、「日本語」のテキストです。한국어 텍스트입니다.\n".repeat(20),
+        ),
+        ("thai", "ภาษาไทยง่ายนิดเดียว ฉันกินข้าว\n".repeat(20)),
+        ("all", all.iter().collect()),
+    ];
+    println!("{:<10} {:>12} {:>12}", "input", "table (ns)", "match (ns)");
+    for &(name, ref input) in inputs.iter() {
+        let input = input.chars().collect::<Vec<_>>();
+        let table = measure(&input, line_break_property);
+        let matched = measure(&input, line_break_property_match);
+        println!("{:<10} {:>12.2} {:>12.2}", name, table, matched);
+    }
+}
@@ -1,88 +1,134 @@
-extern crate regex;
-use regex::Regex;
+extern crate alloc;
+extern crate core;
 use std::collections::HashMap;
-use std::u32;
 
 use std::env;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+use class::{Break, Class};
+use ucd::UcdFile;
+
+// The classes are named as in the annex, which clippy only accepts for the
+// public API of the crate.
+#[allow(dead_code, clippy::upper_case_acronyms)]
+#[path = "src/class.rs"]
+mod class;
+#[allow(dead_code)]
+#[path = "src/rules.rs"]
+mod rules;
+#[allow(dead_code)]
+#[path = "src/states.rs"]
+mod states;
+#[path = "build/ucd.rs"]
+mod ucd;
+#[path = "src/version.rs"]
+mod version;
+
+fn read_data(name: &str, version: (u8, u8, u8)) -> UcdFile {
+    let path = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("unicode-data")
+        .join(format!(
+            "{}-{}.{}.{}.txt",
+            name, version.0, version.1, version.2
+        ));
+    UcdFile::read(&path).unwrap_or_else(fail)
+}
 
-const LINEBREAK: &'static str = include_str!("unicode-data/LineBreak-11.0.0.txt");
-const UNICODEDATA: &'static str = include_str!("unicode-data/UnicodeData.txt");
+// Stop the build because of an error in the data.
+fn fail<T>(error: ucd::Error) -> T {
+    panic!("{}", error)
+}
 
 fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
-    let dest_path = Path::new(&out_dir).join("convert_to_break_class");
-    let mut f = File::create(&dest_path).unwrap();
+    let version = version::UNICODE_VERSION;
+
+    let linebreak = read_data("LineBreak", version);
+    let unicode_data = read_data("UnicodeData", version);
 
     // Extract all codepoints that belong to the general category of Mn or Mc
-    let re1 = Regex::new(r"(?P<codepoint>[0-9A-F]+);[^;]+;(?P<category>(Mn)|(Mc))").unwrap();
-    let mut mn = Vec::new();
-    let mut mc = Vec::new();
-    for caps in re1.captures_iter(UNICODEDATA) {
-        let number =
-            u32::from_str_radix(&caps["codepoint"], 16).expect("Could not parse codepoint");
-        match &caps["category"] {
-            "Mn" => mn.push((number, None)),
-            "Mc" => mc.push((number, None)),
-            _ => unreachable!(),
-        };
+    let mut marks = Vec::new();
+    for category in unicode_data.general_categories().unwrap_or_else(fail) {
+        if category.value == "Mn" || category.value == "Mc" {
+            marks.extend(category.first..=category.last);
+        }
     }
-    let compact_mn = squish(mn);
-    let compact_mc = squish(mc);
 
-    let re2 = Regex::new(
-        r"(?P<left_n>[0-9A-F]+)(\.\.(?P<right_n>[0-9A-F]+))?;(?P<class>[A-Z0-9]+)",
-    ).unwrap();
     let mut hash: HashMap<&str, Vec<(u32, Option<u32>)>> = HashMap::new();
-    for caps in re2.captures_iter(LINEBREAK) {
+    for property in linebreak.properties().unwrap_or_else(fail) {
+        if !PROPERTIES.contains(&property.value) {
+            let message = format!("Unknown Line_Break value {}", property.value);
+            fail::<()>(linebreak.error(property.line, message));
+        }
         // (u32, Some(u32)): 123A..123F
         // (u32, None)     : 123A
-        let numbers: (u32, Option<u32>) = match &caps.name("right_n") {
-            Some(right_n_match) => {
-                let left_n_str = &caps["left_n"];
-                let left_n =
-                    u32::from_str_radix(left_n_str, 16).expect("Could not parse left of range");
-
-                let right_n_str = right_n_match.as_str();
-                let right_n =
-                    u32::from_str_radix(right_n_str, 16).expect("Could not parse right of range");
-                (left_n, Some(right_n))
-            }
-            None => {
-                let n =
-                    u32::from_str_radix(&caps["left_n"], 16).expect("Could not parse codepoint");
-                (n, None)
-            }
+        let numbers = if property.first == property.last {
+            (property.first, None)
+        } else {
+            (property.first, Some(property.last))
         };
-        let class = caps.name("class").unwrap().as_str();
-        hash.entry(class).or_insert(Vec::new()).push(numbers);
+        hash.entry(property.value).or_default().push(numbers);
     }
 
+    // The defaults of code points missing from the data.
+    let defaults = [
+        (0x1F000, Some(0x1FFFD), "ID"),
+        (0x20A0, Some(0x20CF), "PR"),
+    ];
+
+    // The lookup used before the tables, kept for the benchmarks.
+    let dest_path = Path::new(&out_dir).join("line_break_property_match");
+    let mut f = File::create(&dest_path).unwrap();
     write!(f, "match n as u32 {{").unwrap();
-    for (key, value) in hash.into_iter().map(|(key, list)| (key, squish(list))) {
-        match key {
-            "SA" => write!(
-                f,
-                "0x{} => match n as u32 {{0x{}|0x{} => Class::CM,_ => Class::AL}}",
-                value.join(" | 0x"),
-                compact_mn.join(" | 0x"),
-                compact_mc.join(" | 0x")
-            ).unwrap(),
-            "XX" | "SG" | "AI" => write!(f, "0x{} => Class::AL,", value.join(" | 0x")).unwrap(),
-            "CJ" => write!(f, "0x{} => Class::NS,", value.join(" | 0x")).unwrap(),
-            _ => write!(f, "0x{} => Class::{},", value.join(" | 0x"), key).unwrap(),
+    for (key, list) in hash.iter() {
+        let value = squish(list.clone());
+        write!(
+            f,
+            "0x{} => LineBreakProperty::{},",
+            value.join(" | 0x"),
+            key
+        ).unwrap();
+    }
+    for &(left, right, key) in defaults.iter() {
+        let value = format_codepoints(left, right);
+        write!(f, "0x{} => LineBreakProperty::{},", value, key).unwrap();
+    }
+    write!(f, "_ => LineBreakProperty::XX}}").unwrap();
+
+    let mut values = vec![property_index("XX"); 0x110000];
+    let ranges = defaults
+        .iter()
+        .map(|&(left, right, key)| (key, (left, right)))
+        .chain(
+            hash.iter()
+                .flat_map(|(&key, list)| list.iter().map(move |&range| (key, range))),
+        );
+    for (key, (left, right)) in ranges {
+        let index = property_index(key);
+        for value in &mut values[left as usize..=right.unwrap_or(left) as usize] {
+            *value = index;
         }
     }
-    write!(
-        f,
-        "0x1F000...0x1FFFD => Class::ID, 0x20A0...0x20CF => Class::PR, _ => Class::AL}}"
-    ).unwrap();
+    let dest_path = Path::new(&out_dir).join("line_break_property");
+    let mut f = File::create(&dest_path).unwrap();
+    write_property_tables(&mut f, &values);
+
+    let dest_path = Path::new(&out_dir).join("ascii_classes");
+    let mut f = File::create(&dest_path).unwrap();
+    write_ascii_classes(&mut f, &values[..0x80]);
+
+    let dest_path = Path::new(&out_dir).join("marks");
+    let mut f = File::create(&dest_path).unwrap();
+    write_ranges(&mut f, "MARKS", &marks);
 
     let dest_path = Path::new(&out_dir).join("states");
     let mut f = File::create(&dest_path).unwrap();
     write_states(&mut f);
+
+    let dest_path = Path::new(&out_dir).join("rules");
+    let mut f = File::create(&dest_path).unwrap();
+    write_rules(&mut f);
 }
 
 // Convert a list of codepoints / ranges of codepoints into a list with the
@@ -139,424 +185,139 @@ fn squish(values: Vec<(u32, Option<u32>)>) -> Vec<String> {
 
 fn format_codepoints(lower: u32, higher: Option<u32>) -> String {
     match higher {
-        Some(x) => format!("{:X}...0x{:X}", lower, x),
+        Some(x) => format!("{:X}..=0x{:X}", lower, x),
         None => format!("{:X}", lower),
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum Break {
-    Mandatory,
-    Opportunity,
-    Prohibited,
+// The values of `LineBreakProperty` in `src/property.rs`, in the same order.
+const PROPERTIES: [&str; 43] = [
+    "BK", "CR", "LF", "CM", "NL", "SG", "WJ", "ZW", "GL", "SP", "ZWJ", "B2", "BA", "BB", "HY",
+    "CB", "CL", "CP", "EX", "IN", "NS", "OP", "QU", "IS", "NU", "PO", "PR", "SY", "AI", "AL",
+    "CJ", "EB", "EM", "H2", "H3", "HL", "ID", "JL", "JV", "JT", "RI", "SA", "XX",
+];
+
+fn property_index(name: &str) -> u8 {
+    PROPERTIES
+        .iter()
+        .position(|&property| property == name)
+        .unwrap_or_else(|| panic!("Unknown Line_Break value {}", name)) as u8
 }
 
-const NUM_OF_CLASSES: usize = 39;
-
-fn write_states(f: &mut File) {
-    const BK: usize = 0;
-    const CR: usize = 1;
-    const LF: usize = 2;
-    const CM: usize = 3;
-    const NL: usize = 4;
-    const WJ: usize = 5;
-    const ZW: usize = 6;
-    const GL: usize = 7;
-    const SP: usize = 8;
-    const ZWJ: usize = 9;
-    const B2: usize = 10;
-    const BA: usize = 11;
-    const BB: usize = 12;
-    const HY: usize = 13;
-    const CB: usize = 14;
-    const CL: usize = 15;
-    const CP: usize = 16;
-    const EX: usize = 17;
-    const IN: usize = 18;
-    const NS: usize = 19;
-    const OP: usize = 20;
-    const QU: usize = 21;
-    const IS: usize = 22;
-    const NU: usize = 23;
-    const PO: usize = 24;
-    const PR: usize = 25;
-    const SY: usize = 26;
-    const AL: usize = 27;
-    const EB: usize = 28;
-    const EM: usize = 29;
-    const H2: usize = 30;
-    const H3: usize = 31;
-    const HL: usize = 32;
-    const ID: usize = 33;
-    const JL: usize = 34;
-    const JV: usize = 35;
-    const JT: usize = 36;
-    const RI: usize = 37;
-    const XX: usize = 38;
-    const LB8_STATE: usize = NUM_OF_CLASSES + 1;
-    const LB14_STATE: usize = NUM_OF_CLASSES + 2;
-    const LB15_STATE: usize = NUM_OF_CLASSES + 3;
-    const LB16_STATE: usize = NUM_OF_CLASSES + 4;
-    const LB17_STATE: usize = NUM_OF_CLASSES + 5;
-    const LB21A_HY_STATE: usize = NUM_OF_CLASSES + 6;
-    const LB21A_BA_STATE: usize = NUM_OF_CLASSES + 7;
-    const LB30A_EVEN_STATE: usize = NUM_OF_CLASSES + 8;
-    const LB9_EXCEPTIONS: [usize; 8] = [BK, CR, LF, NL, SP, ZW, ZWJ, 39];
-
-    fn break_before(class: usize, b: Break, states: &mut Vec<[(usize, Break); NUM_OF_CLASSES]>) {
-        for state in states.iter_mut() {
-            state[class].1 = b;
-        }
+// The number of code points in a block of the property tables is
+// `1 << BLOCK_BITS`.
+const BLOCK_BITS: usize = 7;
+
+// Write the Line_Break property of every code point as a two-level table.
+// `PROPERTY_INDEX` has the number of the block for each `1 << BLOCK_BITS`
+// code points, and `PROPERTY_BLOCKS` the contents of the distinct blocks, as
+// indices into `PROPERTY_VALUES`.
+fn write_property_tables(f: &mut File, values: &[u8]) {
+    let block_size = 1 << BLOCK_BITS;
+    let mut blocks: Vec<&[u8]> = Vec::new();
+    let mut index = Vec::new();
+    for block in values.chunks(block_size) {
+        let i = blocks.iter().position(|&b| b == block).unwrap_or_else(|| {
+            blocks.push(block);
+            blocks.len() - 1
+        });
+        index.push(i);
     }
-
-    fn break_after(state: usize, b: Break, states: &mut Vec<[(usize, Break); NUM_OF_CLASSES]>) {
-        for c in states[state].iter_mut() {
-            c.1 = b;
-        }
+    assert!(blocks.len() <= 256, "Too many blocks for a u8 index");
+    // Latin-1 is looked up directly in the first blocks.
+    for (i, &block) in index.iter().take(0x100 / block_size).enumerate() {
+        assert_eq!(i, block, "Latin-1 isn't in the first blocks");
     }
 
-    fn not_allowed_between(
-        c1: usize,
-        c2: usize,
-        states: &mut Vec<[(usize, Break); NUM_OF_CLASSES]>,
-    ) {
-        states[c1][c2].1 = Break::Prohibited;
+    writeln!(f, "const BLOCK_BITS: usize = {};", BLOCK_BITS).unwrap();
+    write!(
+        f,
+        "static PROPERTY_VALUES: [LineBreakProperty; {}] = [",
+        PROPERTIES.len()
+    ).unwrap();
+    for property in PROPERTIES.iter() {
+        write!(f, "LineBreakProperty::{},", property).unwrap();
     }
-
-    const LB12A_EXCEPTIONS: [usize; 3] = [SP, BA, HY];
-    let mut states: Vec<[(usize, Break); NUM_OF_CLASSES]> = Vec::new();
-    let mut extra_states: Vec<[(usize, Break); NUM_OF_CLASSES]> = Vec::new();
-
-    for _ in 0..(NUM_OF_CLASSES + 1) {
-        states.push([
-            (0, Break::Opportunity),
-            (1, Break::Opportunity),
-            (2, Break::Opportunity),
-            (3, Break::Opportunity),
-            (4, Break::Opportunity),
-            (5, Break::Opportunity),
-            (6, Break::Opportunity),
-            (7, Break::Opportunity),
-            (8, Break::Opportunity),
-            (9, Break::Opportunity),
-            (10, Break::Opportunity),
-            (11, Break::Opportunity),
-            (12, Break::Opportunity),
-            (13, Break::Opportunity),
-            (14, Break::Opportunity),
-            (15, Break::Opportunity),
-            (16, Break::Opportunity),
-            (17, Break::Opportunity),
-            (18, Break::Opportunity),
-            (19, Break::Opportunity),
-            (20, Break::Opportunity),
-            (21, Break::Opportunity),
-            (22, Break::Opportunity),
-            (23, Break::Opportunity),
-            (24, Break::Opportunity),
-            (25, Break::Opportunity),
-            (26, Break::Opportunity),
-            (27, Break::Opportunity),
-            (28, Break::Opportunity),
-            (29, Break::Opportunity),
-            (30, Break::Opportunity),
-            (31, Break::Opportunity),
-            (32, Break::Opportunity),
-            (33, Break::Opportunity),
-            (34, Break::Opportunity),
-            (35, Break::Opportunity),
-            (36, Break::Opportunity),
-            (37, Break::Opportunity),
-            (38, Break::Opportunity),
-        ]);
+    writeln!(f, "];").unwrap();
+    write!(f, "static PROPERTY_INDEX: [u8; {}] = [", index.len()).unwrap();
+    for i in index {
+        write!(f, "{},", i).unwrap();
     }
-
-    // LB30b
-    not_allowed_between(EB, EM, &mut states);
-
-    // LB30a
-    not_allowed_between(RI, RI, &mut states);
-    states[RI][RI].0 = LB30A_EVEN_STATE;
-
-    // LB30
-    not_allowed_between(AL, OP, &mut states);
-    not_allowed_between(HL, OP, &mut states);
-    not_allowed_between(NU, OP, &mut states);
-
-    not_allowed_between(CP, AL, &mut states);
-    not_allowed_between(CP, HL, &mut states);
-    not_allowed_between(CP, NU, &mut states);
-
-    // LB29
-    not_allowed_between(IS, AL, &mut states);
-    not_allowed_between(IS, HL, &mut states);
-
-    // LB28
-    not_allowed_between(AL, AL, &mut states);
-    not_allowed_between(AL, HL, &mut states);
-    not_allowed_between(HL, AL, &mut states);
-    not_allowed_between(HL, HL, &mut states);
-
-    // LB27
-    not_allowed_between(JL, IN, &mut states);
-    not_allowed_between(JV, IN, &mut states);
-    not_allowed_between(JT, IN, &mut states);
-    not_allowed_between(H2, IN, &mut states);
-    not_allowed_between(H3, IN, &mut states);
-
-    not_allowed_between(JL, PO, &mut states);
-    not_allowed_between(JV, PO, &mut states);
-    not_allowed_between(JT, PO, &mut states);
-    not_allowed_between(H2, PO, &mut states);
-    not_allowed_between(H3, PO, &mut states);
-
-    not_allowed_between(PR, JL, &mut states);
-    not_allowed_between(PR, JV, &mut states);
-    not_allowed_between(PR, JT, &mut states);
-    not_allowed_between(PR, H2, &mut states);
-    not_allowed_between(PR, H3, &mut states);
-
-    // LB26
-    not_allowed_between(JL, JL, &mut states);
-    not_allowed_between(JL, JV, &mut states);
-    not_allowed_between(JL, H2, &mut states);
-    not_allowed_between(JL, H3, &mut states);
-
-    not_allowed_between(JV, JV, &mut states);
-    not_allowed_between(JV, JT, &mut states);
-    not_allowed_between(H2, JV, &mut states);
-    not_allowed_between(H2, JT, &mut states);
-
-    not_allowed_between(JT, JT, &mut states);
-    not_allowed_between(H3, JT, &mut states);
-
-    // LB25
-    not_allowed_between(CL, PO, &mut states);
-    not_allowed_between(CP, PO, &mut states);
-    not_allowed_between(CL, PR, &mut states);
-    not_allowed_between(CP, PR, &mut states);
-    not_allowed_between(NU, PO, &mut states);
-    not_allowed_between(NU, PR, &mut states);
-    not_allowed_between(PO, OP, &mut states);
-    not_allowed_between(PO, NU, &mut states);
-    not_allowed_between(PR, OP, &mut states);
-    not_allowed_between(PR, NU, &mut states);
-    not_allowed_between(HY, NU, &mut states);
-    not_allowed_between(IS, NU, &mut states);
-    not_allowed_between(NU, NU, &mut states);
-    not_allowed_between(SY, NU, &mut states);
-
-    // LB24
-    not_allowed_between(PR, AL, &mut states);
-    not_allowed_between(PR, HL, &mut states);
-    not_allowed_between(PO, AL, &mut states);
-    not_allowed_between(PO, HL, &mut states);
-    not_allowed_between(AL, PR, &mut states);
-    not_allowed_between(AL, PO, &mut states);
-    not_allowed_between(HL, PR, &mut states);
-    not_allowed_between(HL, PO, &mut states);
-
-    // LB23a
-    not_allowed_between(PR, ID, &mut states);
-    not_allowed_between(PR, EB, &mut states);
-    not_allowed_between(PR, EM, &mut states);
-    not_allowed_between(ID, PO, &mut states);
-    not_allowed_between(EB, PO, &mut states);
-    not_allowed_between(EM, PO, &mut states);
-
-    // LB23
-    not_allowed_between(AL, NU, &mut states);
-    not_allowed_between(HL, NU, &mut states);
-    not_allowed_between(NU, AL, &mut states);
-    not_allowed_between(NU, HL, &mut states);
-
-    // LB22
-    not_allowed_between(AL, IN, &mut states);
-    not_allowed_between(HL, IN, &mut states);
-    not_allowed_between(EX, IN, &mut states);
-    not_allowed_between(ID, IN, &mut states);
-    not_allowed_between(EB, IN, &mut states);
-    not_allowed_between(EM, IN, &mut states);
-    not_allowed_between(IN, IN, &mut states);
-    not_allowed_between(NU, IN, &mut states);
-
-    // LB21b
-    not_allowed_between(SY, HL, &mut states);
-
-    // LB21a
-    states[HL][HY].0 = LB21A_HY_STATE;
-    states[HL][BA].0 = LB21A_BA_STATE;
-
-    // LB21
-    break_before(BA, Break::Prohibited, &mut states);
-    break_before(HY, Break::Prohibited, &mut states);
-    break_before(NS, Break::Prohibited, &mut states);
-    break_after(BB, Break::Prohibited, &mut states);
-
-    // LB20
-    break_before(CB, Break::Opportunity, &mut states);
-    break_after(CB, Break::Opportunity, &mut states);
-
-    // LB19
-    break_before(QU, Break::Prohibited, &mut states);
-    break_after(QU, Break::Prohibited, &mut states);
-
-    // LB18
-    break_after(SP, Break::Opportunity, &mut states);
-
-    // LB17
-    not_allowed_between(B2, B2, &mut states);
-    states[B2][B2].1 = Break::Prohibited;
-    states[B2][SP].0 = LB17_STATE;
-
-    // LB16
-    not_allowed_between(CL, NS, &mut states);
-    states[CL][SP].0 = LB16_STATE;
-
-    not_allowed_between(CP, NS, &mut states);
-    states[CP][SP].0 = LB16_STATE;
-
-    // LB15
-    states[QU][OP].1 = Break::Prohibited;
-    states[QU][SP].0 = LB15_STATE;
-
-    // LB14
-    break_after(OP, Break::Prohibited, &mut states);
-    states[OP][SP].0 = LB14_STATE;
-
-    // LB13
-    break_before(CL, Break::Prohibited, &mut states);
-    break_before(CP, Break::Prohibited, &mut states);
-    break_before(EX, Break::Prohibited, &mut states);
-    break_before(IS, Break::Prohibited, &mut states);
-    break_before(SY, Break::Prohibited, &mut states);
-
-    // LB12a
-    for state in states.iter_mut().enumerate().filter_map(|(index, state)| {
-        if LB12A_EXCEPTIONS.contains(&index) {
-            None
-        } else {
-            Some(state)
-        }
-    }) {
-        state[GL].1 = Break::Prohibited;
+    writeln!(f, "];").unwrap();
+    write!(
+        f,
+        "static PROPERTY_BLOCKS: [u8; {}] = [",
+        blocks.len() * block_size
+    ).unwrap();
+    for value in blocks.concat() {
+        write!(f, "{},", value).unwrap();
     }
+    writeln!(f, "];").unwrap();
+}
 
-    // LB12
-    break_after(GL, Break::Prohibited, &mut states);
-
-    // LB11
-    break_after(WJ, Break::Prohibited, &mut states);
-    break_before(WJ, Break::Prohibited, &mut states);
-
-    // LB10
-    states[AL][CM].1 = Break::Prohibited;
-    states[AL][ZWJ].1 = Break::Prohibited;
-
-    states[CM] = states[AL];
-    states[ZWJ] = states[AL];
-
-    // LB9
-    for (i, state) in states.iter_mut().enumerate().filter_map(|(index, state)| {
-        if LB9_EXCEPTIONS.contains(&index) {
-            None
-        } else {
-            Some((index, state))
-        }
-    }) {
-        state[CM] = (i, Break::Prohibited);
-        state[ZWJ] = (i, Break::Prohibited);
+// Write the Line Breaking Class of every ASCII character as `ASCII_CLASSES`.
+// None of them are resolved by LB1, so the class has the name of the property.
+fn write_ascii_classes(f: &mut File, values: &[u8]) {
+    write!(f, "static ASCII_CLASSES: [Class; 128] = [").unwrap();
+    for &value in values {
+        let name = PROPERTIES[value as usize];
+        assert!(
+            !["AI", "CJ", "SA", "SG", "XX"].contains(&name),
+            "ASCII has to be resolved from {}",
+            name
+        );
+        write!(f, "Class::{},", name).unwrap();
     }
+    writeln!(f, "];").unwrap();
+}
 
-    // LB8a
-    break_after(ZWJ, Break::Prohibited, &mut states);
-
-    // LB8
-    break_after(ZW, Break::Opportunity, &mut states);
-    states[ZW][SP].0 = LB8_STATE;
-
-    // LB7
-    break_before(SP, Break::Prohibited, &mut states);
-    break_before(ZW, Break::Prohibited, &mut states);
-
-    // LB6
-    break_before(BK, Break::Prohibited, &mut states);
-    break_before(CR, Break::Prohibited, &mut states);
-    break_before(LF, Break::Prohibited, &mut states);
-    break_before(NL, Break::Prohibited, &mut states);
-
-    // LB5
-    break_after(CR, Break::Mandatory, &mut states);
-    break_after(LF, Break::Mandatory, &mut states);
-    break_after(NL, Break::Mandatory, &mut states);
-    not_allowed_between(CR, LF, &mut states);
-
-    // LB4
-    break_after(BK, Break::Mandatory, &mut states);
-
-    // LB2
-    break_after(NUM_OF_CLASSES, Break::Prohibited, &mut states);
-
-    // Special extra states
-
-    // LB8
-    let mut new_state = states[SP].clone();
-    for part in new_state.iter_mut().enumerate().filter_map(|(i, s)| {
-        if [BK, CR, LF, NL, SP, ZW].contains(&i) {
-            None
-        } else {
-            Some(s)
+// Write the code points as a sorted list of inclusive ranges.
+fn write_ranges(f: &mut File, name: &str, codepoints: &[u32]) {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for &n in codepoints {
+        match ranges.last_mut() {
+            Some(last) if last.1 + 1 == n => last.1 = n,
+            _ => ranges.push((n, n)),
         }
-    }) {
-        part.1 = Break::Opportunity;
-    }
-    extra_states.push(new_state);
-
-    // LB14
-    let mut new_state = states[SP].clone();
-    for part in new_state.iter_mut() {
-        part.1 = Break::Prohibited;
-    }
-    extra_states.push(new_state);
-
-    // LB15
-    let mut new_state = states[SP].clone();
-    new_state[OP].1 = Break::Prohibited;
-    extra_states.push(new_state);
-
-    // LB16
-    let mut new_state = states[SP].clone();
-    new_state[NS].1 = Break::Prohibited;
-    extra_states.push(new_state);
-
-    // LB17
-    let mut new_state = states[SP].clone();
-    new_state[B2].1 = Break::Prohibited;
-    extra_states.push(new_state);
-
-    // LB21a
-    let mut hy_state = states[HY].clone();
-    for part in hy_state.iter_mut() {
-        part.1 = Break::Prohibited;
     }
-    let mut ba_state = states[BA].clone();
-    for part in ba_state.iter_mut() {
-        part.1 = Break::Prohibited;
+    write!(f, "static {}: [(u32, u32); {}] = [", name, ranges.len()).unwrap();
+    for (left, right) in ranges {
+        write!(f, "(0x{:X}, 0x{:X}),", left, right).unwrap();
     }
-    extra_states.push(hy_state);
-    extra_states.push(ba_state);
-
-    // LB30a
-    let mut even_state = states[RI].clone();
-    even_state[RI] = (RI, Break::Opportunity);
-    extra_states.push(even_state);
+    write!(f, "];").unwrap();
+}
 
-    states.extend(extra_states.into_iter());
-    write!(f, "const NUM_OF_CLASSES: usize = {};\nconst STATES: [[(usize, Break); NUM_OF_CLASSES]; {}] = [", NUM_OF_CLASSES, states.len()).unwrap();
+fn write_states(f: &mut File) {
+    let states = rules::RuleSetBuilder::new().build().states;
+    write!(
+        f,
+        "static STATES: [[u8; NUM_OF_CLASSES]; {}] = [",
+        states.len()
+    ).unwrap();
     for state in states {
         write!(f, "[").unwrap();
         for value in state.iter() {
-            write!(f, "({}, Break::{:?}),", value.0, value.1).unwrap();
+            write!(f, "{},", value).unwrap();
+        }
+        write!(f, "],").unwrap();
+    }
+    write!(f, "];").unwrap();
+}
+
+fn write_rules(f: &mut File) {
+    let rules = rules::RuleSetBuilder::new().build().rules;
+    write!(
+        f,
+        "static RULES: [[Option<Rule>; NUM_OF_CLASSES]; {}] = [",
+        rules.len()
+    ).unwrap();
+    for state in rules {
+        write!(f, "[").unwrap();
+        for rule in state.iter() {
+            match rule {
+                Some(rule) => write!(f, "Some(Rule::{:?}),", rule).unwrap(),
+                None => write!(f, "None,").unwrap(),
+            }
         }
         write!(f, "],").unwrap();
     }
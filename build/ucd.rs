@@ -0,0 +1,220 @@
+//! A parser for the files of the [Unicode Character Database] used to generate
+//! the tables.
+//!
+//! This module is included by `build.rs`, so it may only use `std`.
+//!
+//! [Unicode Character Database]: https://www.unicode.org/reports/tr44/
+use std::error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// An error in a file of the UCD, with where it happened.
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    /// The name of the file.
+    pub file: String,
+    /// The number of the line, starting at 1, or 0 for the whole file.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}: {}", self.file, self.message)
+        } else {
+            write!(f, "{}:{}: {}", self.file, self.line, self.message)
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// The value of a property for a range of code points.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Property<'a> {
+    /// The line the value was given on.
+    pub line: usize,
+    pub first: u32,
+    pub last: u32,
+    pub value: &'a str,
+}
+
+/// A line of data, with the code points in its first field.
+struct Entry<'a> {
+    line: usize,
+    first: u32,
+    last: u32,
+    fields: Vec<&'a str>,
+}
+
+/// A file of the UCD.
+///
+/// Lines hold fields separated by `;`, the first of which is a code point or a
+/// range of them, like `0041` or `0000..0008`. Everything after a `#` is a
+/// comment, which includes lines like `# @missing: 0000..10FFFF; XX`.
+#[derive(Debug, Clone)]
+pub struct UcdFile {
+    name: String,
+    contents: String,
+}
+
+impl UcdFile {
+    /// Construct a `UcdFile` from its name, used in errors, and its contents.
+    pub fn new<N, C>(name: N, contents: C) -> UcdFile
+    where
+        N: Into<String>,
+        C: Into<String>,
+    {
+        UcdFile {
+            name: name.into(),
+            contents: contents.into(),
+        }
+    }
+
+    /// Read the file at `path`.
+    pub fn read(path: &Path) -> Result<UcdFile, Error> {
+        let name = path.display().to_string();
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(UcdFile::new(name, contents)),
+            Err(e) => Err(Error {
+                file: name,
+                line: 0,
+                message: format!("Could not read the file: {}", e),
+            }),
+        }
+    }
+
+    /// Construct an error at `line` of the file.
+    pub fn error<M: Into<String>>(&self, line: usize, message: M) -> Error {
+        Error {
+            file: self.name.clone(),
+            line,
+            message: message.into(),
+        }
+    }
+
+    // Parse a code point written as 4 to 6 hexadecimal digits.
+    fn parse_codepoint(&self, line: usize, s: &str) -> Result<u32, Error> {
+        if (4..=6).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            let n = u32::from_str_radix(s, 16).unwrap();
+            if n <= 0x10FFFF {
+                return Ok(n);
+            }
+        }
+        Err(self.error(line, format!("Invalid code point `{}`", s)))
+    }
+
+    // Every line of data, which has at least `fields` fields after the code
+    // points.
+    fn entries(&self, fields: usize) -> Result<Vec<Entry<'_>>, Error> {
+        let mut entries = Vec::new();
+        for (i, text) in self.contents.lines().enumerate() {
+            let line = i + 1;
+            let data = text.split('#').next().unwrap().trim();
+            if data.is_empty() {
+                continue;
+            }
+            let mut parts = data.split(';').map(str::trim);
+            let codepoints = parts.next().unwrap();
+            let (first, last) = match codepoints.find("..") {
+                Some(dots) => (
+                    self.parse_codepoint(line, &codepoints[..dots])?,
+                    self.parse_codepoint(line, &codepoints[dots + 2..])?,
+                ),
+                None => {
+                    let n = self.parse_codepoint(line, codepoints)?;
+                    (n, n)
+                }
+            };
+            if first > last {
+                return Err(self.error(line, format!("Empty range `{}`", codepoints)));
+            }
+            let parts = parts.collect::<Vec<_>>();
+            if parts.len() < fields {
+                return Err(self.error(
+                    line,
+                    format!("Expected {} fields, found {}", fields + 1, parts.len() + 1),
+                ));
+            }
+            entries.push(Entry {
+                line,
+                first,
+                last,
+                fields: parts,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Parse the values of a property given in the second field, as in
+    /// `LineBreak.txt`. A value is made of ASCII letters, digits and `_`.
+    pub fn properties(&self) -> Result<Vec<Property<'_>>, Error> {
+        self.entries(1)?
+            .into_iter()
+            .map(|entry| {
+                let value = entry.fields[0];
+                if value.is_empty()
+                    || !value
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b == b'_')
+                {
+                    return Err(self.error(entry.line, format!("Invalid value `{}`", value)));
+                }
+                Ok(Property {
+                    line: entry.line,
+                    first: entry.first,
+                    last: entry.last,
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    /// Parse the General_Category of `UnicodeData.txt`.
+    ///
+    /// Ranges are given there as two lines, with names ending in `First>` and
+    /// `Last>`, which are joined.
+    pub fn general_categories(&self) -> Result<Vec<Property<'_>>, Error> {
+        let mut categories: Vec<Property> = Vec::new();
+        let mut range_start: Option<Entry> = None;
+        for entry in self.entries(2)? {
+            let name = entry.fields[0];
+            let category = entry.fields[1];
+            if category.len() != 2 {
+                return Err(self.error(entry.line, format!("Invalid category `{}`", category)));
+            }
+            if let Some(start) = range_start.take() {
+                if !name.ends_with("Last>") || start.fields[1] != category {
+                    return Err(self.error(
+                        entry.line,
+                        format!(
+                            "Expected the end of the range started on line {}",
+                            start.line
+                        ),
+                    ));
+                }
+                categories.push(Property {
+                    line: start.line,
+                    first: start.first,
+                    last: entry.last,
+                    value: category,
+                });
+            } else if name.ends_with("First>") {
+                range_start = Some(entry);
+            } else {
+                categories.push(Property {
+                    line: entry.line,
+                    first: entry.first,
+                    last: entry.last,
+                    value: category,
+                });
+            }
+        }
+        match range_start {
+            Some(start) => Err(self.error(start.line, "The range is never ended")),
+            None => Ok(categories),
+        }
+    }
+}
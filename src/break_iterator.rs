@@ -0,0 +1,150 @@
+use states::{BREAK_BITS, NUM_OF_CLASSES};
+use {line_break_property, states, BreakOpportunities, LineBreakProperty, NoTailoring, Tailoring};
+
+/// Random access to the line break opportunities of a `str`, like the
+/// `BreakIterator` of [ICU].
+///
+/// The positions are byte indices. Both the start and the end of the input
+/// count as boundaries, even though no line break is inserted there.
+///
+/// Instead of running the algorithm from the start of the input, every query
+/// starts from the last safe point before the offset. That is a `char` after
+/// which the state machine is always in the same state, whatever came before
+/// it, like a letter or a mandatory break, but not a space or a combining mark.
+/// Runs of [SA] characters are never used, as the word segmenter needs to see
+/// them from their start.
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::BreakIterator;
+///
+/// let input = "Which is tree?\n木禾";
+/// let breaks = BreakIterator::new(input);
+/// assert_eq!(breaks.following(0), Some(6));
+/// assert_eq!(breaks.following(6), Some(9));
+/// assert_eq!(breaks.preceding(12), Some(9));
+/// assert_eq!(breaks.preceding(6), Some(0));
+/// assert!(breaks.is_boundary(15));
+/// assert!(!breaks.is_boundary(16));
+/// assert_eq!(breaks.following(breaks.last()), None);
+/// ```
+///
+/// [ICU]: https://unicode-org.github.io/icu/userguide/boundaryanalysis/
+/// [SA]: https://www.unicode.org/reports/tr14/#SA
+#[derive(Clone, Debug)]
+pub struct BreakIterator<'a, T = NoTailoring> {
+    input: &'a str,
+    tailoring: T,
+    // The classes after which the state machine is always in the same state.
+    safe: [bool; NUM_OF_CLASSES],
+}
+
+impl<'a> BreakIterator<'a> {
+    /// Construct a `BreakIterator` over a `&str`.
+    #[inline]
+    pub fn new(input: &'a str) -> BreakIterator<'a> {
+        BreakIterator::with_tailoring(input, NoTailoring)
+    }
+}
+
+impl<'a, T: Tailoring> BreakIterator<'a, T> {
+    /// Construct a `BreakIterator` over a `&str`, using a [`Tailoring`].
+    pub fn with_tailoring(input: &'a str, tailoring: T) -> BreakIterator<'a, T> {
+        let mut safe = [false; NUM_OF_CLASSES];
+        {
+            let states = states(&tailoring);
+            for (class, safe) in safe.iter_mut().enumerate() {
+                let next = states[0][class] >> BREAK_BITS;
+                *safe = states
+                    .iter()
+                    .all(|state| state[class] >> BREAK_BITS == next);
+            }
+        }
+        BreakIterator {
+            input,
+            tailoring,
+            safe,
+        }
+    }
+
+    /// The first boundary, which is always `0`.
+    #[inline]
+    pub fn first(&self) -> usize {
+        0
+    }
+
+    /// The last boundary, which is always the length of the input.
+    #[inline]
+    pub fn last(&self) -> usize {
+        self.input.len()
+    }
+
+    /// Find the first boundary after `offset`, or `None` if `offset` is at or
+    /// after the end of the input.
+    pub fn following(&self, offset: usize) -> Option<usize> {
+        if offset >= self.input.len() {
+            return None;
+        }
+        let start = self.safe_point(offset + 1);
+        self.breaks_from(start).find(|&i| i > offset)
+    }
+
+    /// Find the last boundary before `offset`, or `None` if `offset` is `0`.
+    pub fn preceding(&self, offset: usize) -> Option<usize> {
+        if offset == 0 {
+            return None;
+        } else if offset > self.input.len() {
+            return Some(self.input.len());
+        }
+        let mut end = offset;
+        let mut start = self.safe_point(end);
+        loop {
+            let found = self.breaks_from(start).take_while(|&i| i < end).last();
+            if found.is_some() {
+                return found;
+            } else if start == 0 {
+                return Some(0);
+            }
+            // There is no boundary after `start`, but it may be one itself,
+            // which is only known from an earlier safe point.
+            end = start + 1;
+            start = self.safe_point(start);
+        }
+    }
+
+    /// Check if `offset` is a boundary.
+    pub fn is_boundary(&self, offset: usize) -> bool {
+        if offset == 0 || offset == self.input.len() {
+            return true;
+        } else if offset > self.input.len() || !self.input.is_char_boundary(offset) {
+            return false;
+        }
+        let start = self.safe_point(offset);
+        self.breaks_from(start).find(|&i| i >= offset) == Some(offset)
+    }
+
+    // The byte index of the last safe `char` starting before `end`, or `0` if
+    // there is none.
+    fn safe_point(&self, mut end: usize) -> usize {
+        while !self.input.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.input[..end]
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| {
+                self.safe[self.tailoring.class(c) as usize]
+                    && line_break_property(c) != LineBreakProperty::SA
+            })
+            .map_or(0, |(i, _)| i)
+    }
+
+    // The break opportunities after the `char` at byte index `start`, which
+    // has to be `0` or a safe point.
+    fn breaks_from(&self, start: usize) -> impl Iterator<Item = usize> + '_ {
+        BreakOpportunities::resume_at(self.input, &self.tailoring, start)
+            .map(|(i, _)| i)
+            .filter(move |&i| i > start)
+    }
+}
@@ -0,0 +1,187 @@
+use states::{LB25_OP_STATE, SOT_STATE};
+use {Break, BreakOpportunities, Class, NoTailoring, Tailoring};
+
+/// The state of the line breaking algorithm between two chunks of a text.
+///
+/// This lets a text that arrives in pieces, like network frames or the nodes
+/// of a rope, be broken without joining it first. Every chunk is given to
+/// [`ChunkBreaks`] with the state left by the previous one, and
+/// [`ChunkBreaks::state`] gives the state for the next one.
+///
+/// The break before a `char` can depend on the `char`s after it, so a break
+/// isn't always known by the end of the chunk it's in. Such a break is kept in
+/// the state, and given by the chunk that decides it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BreakerState {
+    state: usize,
+    // The number of bytes in the chunks so far.
+    offset: usize,
+    // A break opportunity before an `OP`, which is prohibited by LB25 if the
+    // next `char` that isn't a combining mark is a `NU`.
+    pending: Option<(usize, Break)>,
+}
+
+impl BreakerState {
+    /// Construct the `BreakerState` at the start of a text.
+    #[inline]
+    pub fn new() -> BreakerState {
+        BreakerState {
+            state: SOT_STATE,
+            offset: 0,
+            pending: None,
+        }
+    }
+
+    /// The byte index in the whole text where the next chunk starts.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Default for BreakerState {
+    #[inline]
+    fn default() -> BreakerState {
+        BreakerState::new()
+    }
+}
+
+// The class of the first `char` of `rest` that isn't a combining mark, which
+// decides the break before an `OP`, as in `transition`.
+fn lookahead<T: Tailoring>(tailoring: &T, rest: &str) -> Option<Class> {
+    rest.chars()
+        .map(|c| tailoring.class(c))
+        .find(|&class| class != Class::CM && class != Class::ZWJ)
+}
+
+/// An `Iterator` over the byte indices where a line break could be inserted in
+/// one chunk of a text.
+///
+/// This yields the same items as [`BreakOpportunities`] on the whole text,
+/// with indices into the whole text, but one chunk at a time. As the break
+/// after the last `char` of a chunk depends on the first `char` of the next
+/// one, it's given by the next chunk. The break at the end of the text is only
+/// given by a chunk marked with [`end_of_text`](ChunkBreaks::end_of_text),
+/// which may be empty.
+///
+/// A `WordSegmenter` only sees the part of a run of [SA] characters that is
+/// in the chunk, so with one the chunks should not be split inside such runs.
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{byte_line_breaks, BreakerState, ChunkBreaks};
+///
+/// let chunks = ["Price: $", "(1,2", "34.56)\nDo", "ne"];
+/// let mut state = BreakerState::new();
+/// let mut breaks = Vec::new();
+/// for chunk in chunks.iter() {
+///     let mut chunk_breaks = ChunkBreaks::new(chunk, state);
+///     breaks.extend(&mut chunk_breaks);
+///     state = chunk_breaks.state();
+/// }
+/// breaks.extend(ChunkBreaks::new("", state).end_of_text());
+/// assert_eq!(breaks, byte_line_breaks(&chunks.concat()));
+/// ```
+///
+/// [SA]: https://www.unicode.org/reports/tr14/#SA
+#[derive(Clone, Debug)]
+pub struct ChunkBreaks<'a, T = NoTailoring> {
+    breaks: BreakOpportunities<'a, T>,
+    // The number of bytes in the chunks before this one.
+    offset: usize,
+    pending: Option<(usize, Break)>,
+    end_of_text: bool,
+}
+
+impl<'a> ChunkBreaks<'a> {
+    /// Construct a `ChunkBreaks` from a chunk and the state left by the
+    /// previous chunk.
+    #[inline]
+    pub fn new(chunk: &'a str, state: BreakerState) -> ChunkBreaks<'a> {
+        ChunkBreaks::with_tailoring(chunk, state, NoTailoring)
+    }
+}
+
+impl<'a, T: Tailoring> ChunkBreaks<'a, T> {
+    /// Construct a `ChunkBreaks` from a chunk and the state left by the
+    /// previous chunk, using a [`Tailoring`].
+    ///
+    /// The tailoring should be the same for every chunk of a text.
+    pub fn with_tailoring(chunk: &'a str, state: BreakerState, tailoring: T) -> ChunkBreaks<'a, T> {
+        ChunkBreaks {
+            breaks: BreakOpportunities {
+                current_state: state.state,
+                // Whether the break at the end of the text has been given.
+                finished: false,
+                ..BreakOpportunities::with_tailoring(chunk, tailoring)
+            },
+            offset: state.offset,
+            pending: state.pending,
+            end_of_text: false,
+        }
+    }
+
+    /// Mark the chunk as the last one of the text, so that the breaks that
+    /// depend on what follows are decided and the break at the end is given.
+    #[inline]
+    pub fn end_of_text(mut self) -> ChunkBreaks<'a, T> {
+        self.end_of_text = true;
+        self
+    }
+
+    /// Get the state after the part of the chunk that has been iterated over.
+    ///
+    /// Once the iterator is exhausted, this is the state to give with the next
+    /// chunk. If it's taken earlier, the next chunk has to start at
+    /// [`BreakerState::offset`] instead.
+    #[inline]
+    pub fn state(&self) -> BreakerState {
+        BreakerState {
+            state: self.breaks.current_state,
+            offset: self.offset + self.breaks.position,
+            pending: self.pending,
+        }
+    }
+}
+
+impl<'a, T: Tailoring> Iterator for ChunkBreaks<'a, T> {
+    type Item = (usize, Break);
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((i, br)) = self.pending {
+            let rest = &self.breaks.input[self.breaks.position..];
+            match lookahead(&self.breaks.tailoring, rest) {
+                Some(class) => {
+                    self.pending = None;
+                    if class != Class::NU {
+                        return Some((i, br));
+                    }
+                }
+                None if self.end_of_text => {
+                    self.pending = None;
+                    return Some((i, br));
+                }
+                None => {}
+            }
+        }
+        while let Some((i, br)) = self.breaks.next_break() {
+            // When the `char` deciding the break before an `OP` is in a later
+            // chunk, the break waits for it. Only combining marks come in
+            // between, so no other break is given before it.
+            if self.breaks.current_state == LB25_OP_STATE && !self.end_of_text {
+                let rest = &self.breaks.input[self.breaks.position..];
+                if lookahead(&self.breaks.tailoring, rest).is_none() {
+                    self.pending = Some((self.offset + i, br));
+                    continue;
+                }
+            }
+            return Some((self.offset + i, br));
+        }
+        let end = self.offset + self.breaks.input.len();
+        if self.end_of_text && !self.breaks.finished && end > 0 {
+            self.breaks.finished = true;
+            return Some((end, Break::Opportunity));
+        }
+        None
+    }
+}
@@ -0,0 +1,106 @@
+//! The Line Breaking Classes and the breaks between them.
+//!
+//! This module is also included by `build.rs`, for `rules`.
+
+/// A [Line Breaking Class].
+///
+/// Interacting directly with Line Breaking Classes is usually not neccessary
+/// unless you want to implement something similar to [`LineBreaks`].
+///
+/// For converting `char` to `Class`, see [`convert_to_break_class`].
+///
+/// Some classes are missing because of [LB1], see [`line_break_property`] for
+/// the values before it. These are [SG] (invalid in any
+/// input), [SA] (treated as [CM] or [AL] depending on its General Category),
+/// [CJ] (treated as [NS]), [XX] and [SG] (both treated as [AL]). [AI] is
+/// treated as [AL] too, but is kept by [`original_break_class`] so that it can
+/// be resolved differently, like by [`EastAsianContext`].
+///
+/// [SG]: https://www.unicode.org/reports/tr14/#SG
+/// [SA]: https://www.unicode.org/reports/tr14/#SA
+/// [CM]: https://www.unicode.org/reports/tr14/#CM
+/// [AL]: https://www.unicode.org/reports/tr14/#AL
+/// [CJ]: https://www.unicode.org/reports/tr14/#CJ
+/// [NS]: https://www.unicode.org/reports/tr14/#NS
+/// [XX]: https://www.unicode.org/reports/tr14/#XX
+/// [SG]: https://www.unicode.org/reports/tr14/#SG
+/// [AI]: https://www.unicode.org/reports/tr14/#AI
+/// [AL]: https://www.unicode.org/reports/tr14/#AL
+/// [Line Breaking Class]: https://www.unicode.org/reports/tr14/#Table1
+/// [LB1]: https://www.unicode.org/reports/tr14/#LB1
+/// [`LineBreaks`]: crate::LineBreaks
+/// [`convert_to_break_class`]: crate::convert_to_break_class
+/// [`line_break_property`]: crate::line_break_property
+/// [`original_break_class`]: crate::original_break_class
+/// [`EastAsianContext`]: crate::EastAsianContext
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum Class {
+    // Non-tailorable Line Breaking Classes
+    BK = 0, // Mandatory Break
+    CR = 1, // Carriage Return
+    LF = 2, // Line Feed
+    CM = 3, // Combining Mark
+    NL = 4, // Next Line
+    // SG,  // Surrogate - Not used
+    WJ = 5,  // Word Joiner
+    ZW = 6,  // Zero Width Space
+    GL = 7,  // Non-breaking ("Glue")
+    SP = 8,  // Space
+    ZWJ = 9, // Zero Width Joiner
+
+    // Break Opportunities
+    B2 = 10, // Break Opportunity Before and After
+    BA = 11, // Break After
+    BB = 12, // Break Before
+    HY = 13, // Hyphen
+    CB = 14, // Contingent Break After
+
+    // Characters Prohibiting Certain Breaks
+    CL = 15, // Clone Punctuation
+    CP = 16, // Close Parenthesis
+    EX = 17, // Exclamation/Interrogation
+    IN = 18, // Inseparable
+    NS = 19, // Nonstarter
+    OP = 20, // Open Punctuation
+    QU = 21, // Quatation
+
+    // Numeric Context
+    IS = 22, // Infix Numeric Separator
+    NU = 23, // Numeric
+    PO = 24, // Postfix Numeric
+    PR = 25, // Prefix Numeric
+    SY = 26, // Symbols Allowing Break After
+
+    // Other Characters
+    AL = 27, // Alphabetic
+    // CJ, // Conditional Japanese Starter - Not used
+    EB = 28, // Emoji Base
+    EM = 29, // Emoji Modifier
+    H2 = 30, // Hangul LV Syllable
+    H3 = 31, // Hangul LVT Syllable
+    HL = 32, // Hebrew Letter
+    ID = 33, // Ideographic
+    JL = 34, // Hangul L Jamo
+    JV = 35, // Hangul V Jamo
+    JT = 36, // Hangul T Jamo
+    RI = 37, // Regional Indicator
+    // SA, // Complex Context Dependent (South East Asian) - Not used
+    XX = 38, // Unknown
+    AI = 39, // Ambiguous (Alphabetic or Ideographic)
+}
+
+/// Used by [`LineBreaks`] to specify whether a break is allowed or not.
+///
+/// `Mandatory` is where it is expected to be a line break, `Opportunity` is
+/// where it is allowed to be a line break and `Prohibited` is where a line
+/// break isn't allowed.
+///
+/// [`LineBreaks`]: crate::LineBreaks
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum Break {
+    Mandatory = 0,
+    Opportunity = 1,
+    Prohibited = 2,
+}
@@ -0,0 +1,279 @@
+use core::char::{self, REPLACEMENT_CHARACTER};
+use core::iter::FusedIterator;
+use segmenter::ComplexContext;
+use states::SOT_STATE;
+use {transition, Break, NoTailoring, Tailoring};
+
+/// A code unit of UTF-32, which is either a `char` or a `u32`.
+///
+/// This trait is sealed, so that a UTF-16 or UTF-8 input can't be given to
+/// [`Utf32Breaks`] by mistake. Use [`Utf16Breaks`] for UTF-16 instead.
+///
+/// ```compile_fail
+/// use uax_14::Utf32Breaks;
+///
+/// let utf16 = "a🌲 b".encode_utf16().collect::<Vec<u16>>();
+/// let breaks = Utf32Breaks::new(&utf16).collect::<Vec<_>>();
+/// ```
+pub trait Utf32Unit: Copy + Into<u32> + private::Sealed {}
+
+impl Utf32Unit for char {}
+impl Utf32Unit for u32 {}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for char {}
+    impl Sealed for u32 {}
+}
+
+/// The `char`s of a UTF-16 input with the index of their first code unit, like
+/// `CharIndices` for a `str`.
+///
+/// A lone surrogate is decoded as `U+FFFD REPLACEMENT CHARACTER`.
+#[derive(Clone, Debug)]
+struct Utf16Indices<'a> {
+    input: &'a [u16],
+    position: usize,
+}
+
+impl<'a> Iterator for Utf16Indices<'a> {
+    type Item = (usize, char);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.position;
+        let unit = *self.input.get(i)?;
+        self.position += 1;
+        let c = match unit {
+            0xD800..=0xDBFF => match self.input.get(i + 1) {
+                Some(&low @ 0xDC00..=0xDFFF) => {
+                    self.position += 1;
+                    let n = 0x10000 + ((unit as u32 - 0xD800) << 10) + (low as u32 - 0xDC00);
+                    char::from_u32(n).unwrap()
+                }
+                _ => REPLACEMENT_CHARACTER,
+            },
+            _ => char::from_u32(unit as u32).unwrap_or(REPLACEMENT_CHARACTER),
+        };
+        Some((i, c))
+    }
+}
+
+/// The `char`s of a UTF-32 input with their index.
+///
+/// A value that isn't a Unicode scalar value, like a surrogate, is decoded as
+/// `U+FFFD REPLACEMENT CHARACTER`.
+#[derive(Clone, Debug)]
+struct Utf32Indices<'a, U: 'a> {
+    input: &'a [U],
+    position: usize,
+}
+
+impl<'a, U: Utf32Unit> Iterator for Utf32Indices<'a, U> {
+    type Item = (usize, char);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.position;
+        let unit = *self.input.get(i)?;
+        self.position += 1;
+        Some((
+            i,
+            char::from_u32(unit.into()).unwrap_or(REPLACEMENT_CHARACTER),
+        ))
+    }
+}
+
+/// The line breaking algorithm over the `char`s given by `iter`, as done by
+/// `BreakOpportunities` for a `str`.
+#[derive(Clone, Debug)]
+struct Breaks<I, T> {
+    iter: I,
+    // The length of the input, in code units.
+    len: usize,
+    current_state: usize,
+    finished: bool,
+    complex: ComplexContext,
+    tailoring: T,
+}
+
+impl<I, T> Breaks<I, T>
+where
+    I: Iterator<Item = (usize, char)> + Clone,
+    T: Tailoring,
+{
+    fn new(iter: I, len: usize, tailoring: T) -> Breaks<I, T> {
+        Breaks {
+            iter,
+            len,
+            current_state: SOT_STATE,
+            finished: len == 0,
+            complex: ComplexContext::default(),
+            tailoring,
+        }
+    }
+}
+
+impl<I, T> Iterator for Breaks<I, T>
+where
+    I: Iterator<Item = (usize, char)> + Clone,
+    T: Tailoring,
+{
+    type Item = (usize, Break);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let from = self.iter.clone();
+            let (i, c) = match self.iter.next() {
+                Some(item) => item,
+                None => break,
+            };
+            let rest = self.iter.clone().map(|(_, c)| c);
+            let class = self.tailoring.class(c);
+            let (new_state, br) = transition(&self.tailoring, self.current_state, class, rest);
+            self.current_state = new_state;
+            let br = self
+                .complex
+                .tailor_break_chars(&self.tailoring, from, self.len, i, class, br);
+            if br != Break::Prohibited {
+                return Some((i, br));
+            }
+        }
+        if self.finished {
+            None
+        } else {
+            self.finished = true;
+            Some((self.len, Break::Opportunity))
+        }
+    }
+}
+
+/// An `Iterator` over the indices where a line break could be inserted in a
+/// UTF-16 input, like the strings of JavaScript or the Windows API.
+///
+/// This yields the same items as [`BreakOpportunities`] would for the same
+/// text, with indices counted in code units instead of bytes. A surrogate
+/// pair is a single `char`, so there is never a break between its two code
+/// units.
+///
+/// A lone surrogate is treated as `U+FFFD REPLACEMENT CHARACTER`, as by
+/// [`String::from_utf16_lossy`]. [`Tailoring::class`] is asked about that
+/// `char` instead, which gives [AL] by default, like [LB1] does for [SG].
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{Break, Utf16Breaks};
+///
+/// let input = "Which is 🌲?".encode_utf16().collect::<Vec<u16>>();
+/// let breaks = Utf16Breaks::new(&input).collect::<Vec<_>>();
+/// assert_eq!(
+///     breaks,
+///     [
+///         (6, Break::Opportunity),
+///         (9, Break::Opportunity),
+///         (input.len(), Break::Opportunity)
+///     ]
+/// );
+/// ```
+///
+/// [`BreakOpportunities`]: crate::BreakOpportunities
+/// [`String::from_utf16_lossy`]: https://doc.rust-lang.org/std/string/struct.String.html#method.from_utf16_lossy
+/// [AL]: https://www.unicode.org/reports/tr14/#AL
+/// [LB1]: https://www.unicode.org/reports/tr14/#LB1
+/// [SG]: https://www.unicode.org/reports/tr14/#SG
+#[derive(Clone, Debug)]
+pub struct Utf16Breaks<'a, T = NoTailoring> {
+    breaks: Breaks<Utf16Indices<'a>, T>,
+}
+
+impl<'a> Utf16Breaks<'a> {
+    /// Construct a `Utf16Breaks` from a `&[u16]`.
+    #[inline]
+    pub fn new(input: &'a [u16]) -> Utf16Breaks<'a> {
+        Utf16Breaks::with_tailoring(input, NoTailoring)
+    }
+}
+
+impl<'a, T: Tailoring> Utf16Breaks<'a, T> {
+    /// Construct a `Utf16Breaks` from a `&[u16]`, using a [`Tailoring`].
+    #[inline]
+    pub fn with_tailoring(input: &'a [u16], tailoring: T) -> Utf16Breaks<'a, T> {
+        let iter = Utf16Indices { input, position: 0 };
+        Utf16Breaks {
+            breaks: Breaks::new(iter, input.len(), tailoring),
+        }
+    }
+}
+
+impl<'a, T: Tailoring> Iterator for Utf16Breaks<'a, T> {
+    type Item = (usize, Break);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.breaks.next()
+    }
+}
+
+impl<'a, T: Tailoring> FusedIterator for Utf16Breaks<'a, T> {}
+
+/// An `Iterator` over the indices where a line break could be inserted in a
+/// UTF-32 input, either a `&[char]` or a `&[u32]`.
+///
+/// This yields the same items as [`BreakOpportunities`] would for the same
+/// text, with indices counted in `char`s instead of bytes.
+///
+/// A `u32` that isn't a Unicode scalar value, like a surrogate, is treated as
+/// `U+FFFD REPLACEMENT CHARACTER`, as for the lone surrogates of
+/// [`Utf16Breaks`].
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{Break, Utf32Breaks};
+///
+/// let input = "Which is 🌲?".chars().collect::<Vec<char>>();
+/// let breaks = Utf32Breaks::new(&input).collect::<Vec<_>>();
+/// assert_eq!(
+///     breaks,
+///     [
+///         (6, Break::Opportunity),
+///         (9, Break::Opportunity),
+///         (input.len(), Break::Opportunity)
+///     ]
+/// );
+/// ```
+///
+/// [`BreakOpportunities`]: crate::BreakOpportunities
+#[derive(Clone, Debug)]
+pub struct Utf32Breaks<'a, U: 'a, T = NoTailoring> {
+    breaks: Breaks<Utf32Indices<'a, U>, T>,
+}
+
+impl<'a, U: Utf32Unit> Utf32Breaks<'a, U> {
+    /// Construct a `Utf32Breaks` from a `&[char]` or a `&[u32]`.
+    #[inline]
+    pub fn new(input: &'a [U]) -> Utf32Breaks<'a, U> {
+        Utf32Breaks::with_tailoring(input, NoTailoring)
+    }
+}
+
+impl<'a, U: Utf32Unit, T: Tailoring> Utf32Breaks<'a, U, T> {
+    /// Construct a `Utf32Breaks` from a `&[char]` or a `&[u32]`, using a
+    /// [`Tailoring`].
+    #[inline]
+    pub fn with_tailoring(input: &'a [U], tailoring: T) -> Utf32Breaks<'a, U, T> {
+        let iter = Utf32Indices { input, position: 0 };
+        Utf32Breaks {
+            breaks: Breaks::new(iter, input.len(), tailoring),
+        }
+    }
+}
+
+impl<'a, U: Utf32Unit, T: Tailoring> Iterator for Utf32Breaks<'a, U, T> {
+    type Item = (usize, Break);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.breaks.next()
+    }
+}
+
+impl<'a, U: Utf32Unit, T: Tailoring> FusedIterator for Utf32Breaks<'a, U, T> {}
@@ -0,0 +1,369 @@
+use {
+    line_break_property, original_break_class, Break, Class, EastAsianContext, LineBreakProperty,
+    Rule, RuleSetBuilder, StateTable, Tailoring, ASCII_CLASSES,
+};
+
+/// Check if a `char` is of the [CJ] Line Breaking Class, like the small kana
+/// and the prolonged sound mark of Japanese.
+///
+/// [CJ]: https://www.unicode.org/reports/tr14/#CJ
+fn is_conditional_japanese_starter(n: char) -> bool {
+    line_break_property(n) == LineBreakProperty::CJ
+}
+
+// Hyphens that can be broken before in Chinese and Japanese, unless strict.
+fn is_cjk_hyphen(c: char) -> bool {
+    matches!(c, '\u{2010}' | '\u{2013}' | '\u{301C}' | '\u{30A0}')
+}
+
+fn is_iteration_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3005}' | '\u{303B}' | '\u{309D}' | '\u{309E}' | '\u{30FD}' | '\u{30FE}'
+    )
+}
+
+// Centered punctuation marks, which can be broken before in Chinese and
+// Japanese when loose.
+fn is_cjk_centered_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        '\u{30FB}'
+            | '\u{FF1A}'
+            | '\u{FF1B}'
+            | '\u{FF65}'
+            | '\u{203C}'
+            | '\u{2047}'
+            | '\u{2048}'
+            | '\u{2049}'
+            | '\u{FF01}'
+            | '\u{FF1F}'
+    )
+}
+
+// Suffixes that can be broken before in Chinese and Japanese when loose, and
+// prefixes that can be broken after. They are the `PO` and `PR` characters
+// with an East Asian Width of Ambiguous, Fullwidth or Wide.
+fn is_cjk_wide_postfix(c: char) -> bool {
+    matches!(
+        c,
+        '\u{B0}'
+            | '\u{2030}'
+            | '\u{2032}'
+            | '\u{2033}'
+            | '\u{2035}'
+            | '\u{2103}'
+            | '\u{2109}'
+            | '\u{FE6A}'
+            | '\u{FF05}'
+            | '\u{FFE0}'
+    )
+}
+
+fn is_cjk_wide_prefix(c: char) -> bool {
+    matches!(
+        c,
+        '\u{A4}'
+            | '\u{B1}'
+            | '\u{20AC}'
+            | '\u{2116}'
+            | '\u{FE69}'
+            | '\u{FF04}'
+            | '\u{FFE1}'
+            | '\u{FFE5}'
+            | '\u{FFE6}'
+    )
+}
+
+// The state machine only knows classes, so the wide prefixes and postfixes are
+// given classes that `CssOptions` otherwise never gives, which act as `PR` and
+// `PO` except on the side where the breaks are allowed.
+const WIDE_PREFIX: Class = Class::XX;
+const WIDE_POSTFIX: Class = Class::AI;
+
+// The rules that keep prefixes and postfixes together with what's next to
+// them.
+const AFFIX_RULES: [Rule; 4] = [Rule::LB23a, Rule::LB24, Rule::LB25, Rule::LB27];
+
+/// The strictness of line breaking, as given by the [CSS `line-break`]
+/// property.
+///
+/// [CSS `line-break`]: https://www.w3.org/TR/css-text-3/#line-break-property
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Strictness {
+    /// The rules of the annex, which resolve [CJ] to [NS], so there is never a
+    /// break before small kana or the prolonged sound mark.
+    ///
+    /// [CJ]: https://www.unicode.org/reports/tr14/#CJ
+    /// [NS]: https://www.unicode.org/reports/tr14/#NS
+    #[default]
+    Strict,
+    /// Like `Strict`, but [CJ] is resolved to [ID]. For Chinese and Japanese,
+    /// breaks are also allowed before the hyphens U+2010, U+2013, U+301C and
+    /// U+30A0.
+    ///
+    /// [CJ]: https://www.unicode.org/reports/tr14/#CJ
+    /// [ID]: https://www.unicode.org/reports/tr14/#ID
+    Normal,
+    /// Like `Normal`, but breaks are also allowed before iteration marks and
+    /// between inseparable characters ([IN]). For Chinese and Japanese, breaks
+    /// are also allowed before centered punctuation marks and wide suffixes,
+    /// and after wide prefixes.
+    ///
+    /// [IN]: https://www.unicode.org/reports/tr14/#IN
+    Loose,
+    /// A break is allowed between any two characters, except before combining
+    /// marks, inside pairs of regional indicators and between an emoji base and
+    /// its modifier. Mandatory breaks are kept.
+    Anywhere,
+}
+
+/// How breaks inside words are handled, as given by the [CSS `word-break`]
+/// property.
+///
+/// [CSS `word-break`]: https://www.w3.org/TR/css-text-3/#word-break-property
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WordBreak {
+    /// Words are broken according to the usual rules.
+    #[default]
+    Normal,
+    /// Breaks are allowed inside words, by treating letters and numbers ([AL],
+    /// [HL], [NU] and [SA] resolved to [AL]) as ideographic characters ([ID]).
+    ///
+    /// [AL]: https://www.unicode.org/reports/tr14/#AL
+    /// [HL]: https://www.unicode.org/reports/tr14/#HL
+    /// [NU]: https://www.unicode.org/reports/tr14/#NU
+    /// [SA]: https://www.unicode.org/reports/tr14/#SA
+    /// [ID]: https://www.unicode.org/reports/tr14/#ID
+    BreakAll,
+    /// Breaks are prohibited inside words, which means between two letters,
+    /// numbers or ideographic characters, including Hangul. Ignored with
+    /// `Strictness::Anywhere`.
+    KeepAll,
+}
+
+// The classes of the letters that `WordBreak::KeepAll` keeps together.
+const KEEP_ALL_CLASSES: [Class; 9] = [
+    Class::AL,
+    Class::HL,
+    Class::NU,
+    Class::ID,
+    Class::H2,
+    Class::H3,
+    Class::JL,
+    Class::JV,
+    Class::JT,
+];
+
+// The rules ignored by `Strictness::Anywhere`, which is everything that isn't
+// about mandatory breaks or keeping characters together.
+const ANYWHERE_DISABLED: [Rule; 27] = [
+    Rule::LB7,
+    Rule::LB8,
+    Rule::LB8a,
+    Rule::LB11,
+    Rule::LB12,
+    Rule::LB12a,
+    Rule::LB13,
+    Rule::LB14,
+    Rule::LB15,
+    Rule::LB16,
+    Rule::LB17,
+    Rule::LB18,
+    Rule::LB19,
+    Rule::LB20,
+    Rule::LB21,
+    Rule::LB21a,
+    Rule::LB21b,
+    Rule::LB22,
+    Rule::LB23,
+    Rule::LB23a,
+    Rule::LB24,
+    Rule::LB25,
+    Rule::LB26,
+    Rule::LB27,
+    Rule::LB28,
+    Rule::LB29,
+    Rule::LB30,
+];
+
+/// A [`Tailoring`] following the line breaking properties of [CSS Text].
+///
+/// The [`Strictness`] is chosen as with the `line-break` property. Some of its
+/// rules only apply to Chinese and Japanese, which is set with
+/// [`chinese_or_japanese`]. Breaks inside words are controlled with
+/// [`WordBreak`], as with the `word-break` property. Ambiguous characters are
+/// resolved with the [`EastAsianContext`] set with [`east_asian`].
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{byte_line_breaks_tailored, Break, CssOptions, Strictness};
+///
+/// // Small kana (ぁ) and iteration marks (々)
+/// let input = "あぁ人々";
+/// let strict = CssOptions::new();
+/// let normal = CssOptions::new().line_break(Strictness::Normal);
+/// let loose = CssOptions::new().line_break(Strictness::Loose);
+/// assert_eq!(
+///     byte_line_breaks_tailored(input, &strict),
+///     [(6, Break::Opportunity), (12, Break::Opportunity)]
+/// );
+/// assert_eq!(
+///     byte_line_breaks_tailored(input, &normal),
+///     [
+///         (3, Break::Opportunity),
+///         (6, Break::Opportunity),
+///         (12, Break::Opportunity)
+///     ]
+/// );
+/// assert_eq!(
+///     byte_line_breaks_tailored(input, &loose),
+///     [
+///         (3, Break::Opportunity),
+///         (6, Break::Opportunity),
+///         (9, Break::Opportunity),
+///         (12, Break::Opportunity)
+///     ]
+/// );
+/// ```
+///
+/// ```
+/// use uax_14::{byte_line_breaks_tailored, Break, CssOptions, WordBreak};
+///
+/// let input = "그 사람은 일을";
+/// let keep_all = CssOptions::new().word_break(WordBreak::KeepAll);
+/// assert_eq!(
+///     byte_line_breaks_tailored(input, &keep_all),
+///     [(4, Break::Opportunity), (14, Break::Opportunity), (input.len(), Break::Opportunity)]
+/// );
+/// ```
+///
+/// [CSS Text]: https://www.w3.org/TR/css-text-3/
+/// [`chinese_or_japanese`]: CssOptions::chinese_or_japanese
+/// [`east_asian`]: CssOptions::east_asian
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CssOptions {
+    strictness: Strictness,
+    word_break: WordBreak,
+    chinese_or_japanese: bool,
+    east_asian: EastAsianContext,
+    table: Option<StateTable>,
+}
+
+impl CssOptions {
+    /// Construct a `CssOptions` with `Strictness::Strict` and
+    /// `WordBreak::Normal`, for text that isn't Chinese or Japanese nor East
+    /// Asian.
+    pub fn new() -> CssOptions {
+        CssOptions::default()
+    }
+
+    /// Use `strictness`, as with the `line-break` property.
+    pub fn line_break(mut self, strictness: Strictness) -> CssOptions {
+        self.strictness = strictness;
+        self.table = self.build_table();
+        self
+    }
+
+    /// Use `word_break`, as with the `word-break` property.
+    pub fn word_break(mut self, word_break: WordBreak) -> CssOptions {
+        self.word_break = word_break;
+        self.table = self.build_table();
+        self
+    }
+
+    // The state machine for the current options, or `None` if they only
+    // change the resolution of classes.
+    fn build_table(&self) -> Option<StateTable> {
+        let mut builder = RuleSetBuilder::new();
+        if self.strictness == Strictness::Anywhere {
+            for &rule in ANYWHERE_DISABLED.iter() {
+                builder = builder.disable(rule);
+            }
+            return Some(builder.build());
+        }
+        let loose = self.strictness == Strictness::Loose;
+        let keep_all = self.word_break == WordBreak::KeepAll;
+        if !loose && !keep_all {
+            return None;
+        }
+        if loose {
+            builder = builder.break_between(Class::IN, Class::IN, Break::Opportunity);
+        }
+        if loose && self.chinese_or_japanese {
+            builder = builder
+                .relax(WIDE_PREFIX, Class::PR, &AFFIX_RULES, false)
+                .relax(WIDE_POSTFIX, Class::PO, &AFFIX_RULES, true);
+        }
+        if keep_all {
+            for &c1 in KEEP_ALL_CLASSES.iter() {
+                for &c2 in KEEP_ALL_CLASSES.iter() {
+                    builder = builder.not_allowed_between(c1, c2);
+                }
+            }
+        }
+        Some(builder.build())
+    }
+
+    /// Set whether the content language is Chinese or Japanese.
+    pub fn chinese_or_japanese(mut self, chinese_or_japanese: bool) -> CssOptions {
+        self.chinese_or_japanese = chinese_or_japanese;
+        self.table = self.build_table();
+        self
+    }
+
+    /// Resolve ambiguous characters ([AI]) with `context`.
+    ///
+    /// [AI]: https://www.unicode.org/reports/tr14/#AI
+    pub fn east_asian(mut self, context: EastAsianContext) -> CssOptions {
+        self.east_asian = context;
+        self
+    }
+}
+
+impl Tailoring for CssOptions {
+    fn class(&self, c: char) -> Class {
+        let mut class = self.east_asian.resolve(original_break_class(c));
+        if self.word_break == WordBreak::BreakAll
+            && matches!(class, Class::AL | Class::HL | Class::NU)
+        {
+            class = Class::ID;
+        }
+        let loose = match self.strictness {
+            Strictness::Strict | Strictness::Anywhere => return class,
+            Strictness::Normal => false,
+            Strictness::Loose => true,
+        };
+        let cjk = self.chinese_or_japanese;
+        if is_conditional_japanese_starter(c)
+            || (cjk && is_cjk_hyphen(c))
+            || (loose && is_iteration_mark(c))
+            || (loose && cjk && is_cjk_centered_punctuation(c))
+        {
+            Class::ID
+        } else if loose && cjk && is_cjk_wide_prefix(c) {
+            WIDE_PREFIX
+        } else if loose && cjk && is_cjk_wide_postfix(c) {
+            WIDE_POSTFIX
+        } else {
+            class
+        }
+    }
+
+    #[inline]
+    fn state_table(&self) -> Option<&StateTable> {
+        self.table.as_ref()
+    }
+
+    #[inline]
+    fn ascii_classes(&self) -> Option<&[Class; 128]> {
+        // Letters and digits are ideographic with `break-all`, and nothing
+        // else changes the classes of ASCII.
+        if self.word_break == WordBreak::BreakAll {
+            None
+        } else {
+            Some(&ASCII_CLASSES)
+        }
+    }
+}
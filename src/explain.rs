@@ -0,0 +1,133 @@
+use alloc::vec::Vec;
+use segmenter::ComplexContext;
+use states::{NUM_OF_CLASSES, SOT_STATE};
+use {states, transition, unpack, Break, Class, NoTailoring, Rule, Tailoring};
+
+include!(concat!(env!("OUT_DIR"), "/rules"));
+
+/// Why there is or isn't a line break at a position, as given by
+/// [`explain_line_breaks`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Explanation {
+    /// The byte index of the position.
+    pub byte_index: usize,
+    /// The index of the position in `char`s.
+    pub char_index: usize,
+    /// The class of the `char` before the position, or `None` at the start of
+    /// the text.
+    pub before: Option<Class>,
+    /// The class of the `char` after the position, or `None` at the end of the
+    /// text.
+    pub after: Option<Class>,
+    /// The break at the position, which is `Prohibited` where there is none.
+    pub br: Break,
+    /// The rule that decided the break, or `None` for a rule added to a
+    /// [`RuleSetBuilder`].
+    ///
+    /// [`RuleSetBuilder`]: crate::RuleSetBuilder
+    pub rule: Option<Rule>,
+}
+
+// The rules that decided the breaks of the state machine of `tailoring`.
+fn rules<T: Tailoring>(tailoring: &T) -> &[[Option<Rule>; NUM_OF_CLASSES]] {
+    match tailoring.state_table() {
+        Some(table) => &table.rules[..],
+        None => &RULES[..],
+    }
+}
+
+/// Explain the break at every position of a `str`, from the start of the text
+/// to its end, with the rule of the [Line Breaking Algorithm] that decided it.
+///
+/// The breaks are the same as those of [`byte_line_breaks`], with the
+/// positions where a break is prohibited too. The rules are recorded while the
+/// state machine is built, so a break is put down to the first rule that
+/// applies to it, or LB31 if none does. The end of the text is given LB3, and
+/// a break found by a [`WordSegmenter`] LB1. An empty text has no positions.
+///
+/// Where a combining mark is attached to the `char` before it by [LB9], the
+/// rules see the class of that `char`, but [`Explanation::before`] is still
+/// the class of the mark.
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{explain_line_breaks, Break, Class, Rule};
+///
+/// let explanations = explain_line_breaks("a (1");
+/// let rules = explanations
+///     .iter()
+///     .map(|explanation| (explanation.byte_index, explanation.rule))
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     rules,
+///     [
+///         (0, Some(Rule::LB2)),
+///         (1, Some(Rule::LB7)),
+///         (2, Some(Rule::LB18)),
+///         (3, Some(Rule::LB14)),
+///         (4, Some(Rule::LB3))
+///     ]
+/// );
+/// assert_eq!(explanations[2].before, Some(Class::SP));
+/// assert_eq!(explanations[2].after, Some(Class::OP));
+/// assert_eq!(explanations[2].br, Break::Opportunity);
+/// ```
+///
+/// [Line Breaking Algorithm]: https://www.unicode.org/reports/tr14/#Algorithm
+/// [LB9]: https://www.unicode.org/reports/tr14/#LB9
+/// [`byte_line_breaks`]: crate::byte_line_breaks
+/// [`WordSegmenter`]: crate::WordSegmenter
+pub fn explain_line_breaks(input: &str) -> Vec<Explanation> {
+    explain_line_breaks_tailored(input, NoTailoring)
+}
+
+/// Explain the break at every position of a `str`, using a [`Tailoring`].
+///
+/// *See [`explain_line_breaks`].*
+pub fn explain_line_breaks_tailored<T: Tailoring>(input: &str, tailoring: T) -> Vec<Explanation> {
+    let table = states(&tailoring);
+    let rules = rules(&tailoring);
+    let mut complex = ComplexContext::default();
+    let mut state = SOT_STATE;
+    let mut before = None;
+    let mut explanations = Vec::new();
+    for (char_index, (i, c)) in input.char_indices().enumerate() {
+        let class = tailoring.class(c);
+        let rest = input[i + c.len_utf8()..].chars();
+        let (_, table_br) = unpack(table[state][class as usize]);
+        let (new_state, br) = transition(&tailoring, state, class, rest);
+        // The table can't tell the break before an `OP` that is followed by a
+        // `NU`, which `transition` looks ahead for.
+        let mut rule = if br != table_br {
+            Some(Rule::LB25)
+        } else {
+            rules[state][class as usize]
+        };
+        let tailored = complex.tailor_break(&tailoring, input, i, class, br);
+        if tailored != br {
+            rule = Some(Rule::LB1);
+        }
+        explanations.push(Explanation {
+            byte_index: i,
+            char_index,
+            before,
+            after: Some(class),
+            br: tailored,
+            rule,
+        });
+        state = new_state;
+        before = Some(class);
+    }
+    if !input.is_empty() {
+        explanations.push(Explanation {
+            byte_index: input.len(),
+            char_index: explanations.len(),
+            before,
+            after: None,
+            br: Break::Opportunity,
+            rule: Some(Rule::LB3),
+        });
+    }
+    explanations
+}
@@ -0,0 +1,233 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+use segmenter::ComplexContext;
+use states::SOT_STATE;
+use {line_break_property, transition, Break, Class, LineBreakProperty, NoTailoring, Tailoring};
+
+/// The break opportunities of a text that is edited, like a paragraph in an
+/// editor, kept up to date without breaking the whole text again.
+///
+/// Along with the breaks, the state of the state machine before every `char`
+/// is recorded. After an edit, the algorithm is run again from just before the
+/// edit, and stops once it's after the edit and in the same state as before,
+/// as the rest of the breaks can't have changed then.
+///
+/// *See [`Tailoring`] for customising the algorithm.*
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{byte_line_breaks, Break, IncrementalBreaks};
+///
+/// let mut breaks = IncrementalBreaks::new("Which is tree? 木禾夫");
+/// let changes = breaks.edit(6..8, "was a");
+/// assert_eq!(breaks.text(), "Which was a tree? 木禾夫");
+/// assert_eq!(breaks.breaks(), &byte_line_breaks(breaks.text())[..]);
+/// assert_eq!(changes.removed, []);
+/// assert_eq!(changes.added, [(10, Break::Opportunity)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IncrementalBreaks<T = NoTailoring> {
+    text: String,
+    breaks: Vec<(usize, Break)>,
+    // The state before the `char` at every byte index, and after the text at
+    // `text.len()`. Bytes inside a `char` repeat the state before it.
+    states: Vec<u8>,
+    tailoring: T,
+}
+
+/// The break opportunities changed by an edit of [`IncrementalBreaks`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BreakChanges {
+    /// The byte range of the new text where the breaks were found again. The
+    /// breaks outside of it are the same, with the ones after the edit moved
+    /// by the difference in length.
+    pub range: Range<usize>,
+    /// The breaks that are no longer there, at their index in the old text.
+    ///
+    /// A break that is still there but of another kind is in both `removed`
+    /// and `added`.
+    pub removed: Vec<(usize, Break)>,
+    /// The new breaks, at their index in the new text.
+    pub added: Vec<(usize, Break)>,
+}
+
+impl IncrementalBreaks {
+    /// Construct an `IncrementalBreaks` from a `&str`.
+    #[inline]
+    pub fn new(text: &str) -> IncrementalBreaks {
+        IncrementalBreaks::with_tailoring(text, NoTailoring)
+    }
+}
+
+impl<T: Tailoring> IncrementalBreaks<T> {
+    /// Construct an `IncrementalBreaks` from a `&str`, using a [`Tailoring`].
+    pub fn with_tailoring(text: &str, tailoring: T) -> IncrementalBreaks<T> {
+        let mut out = IncrementalBreaks {
+            text: String::new(),
+            breaks: Vec::new(),
+            states: vec![SOT_STATE as u8],
+            tailoring,
+        };
+        out.edit(0..0, text);
+        out
+    }
+
+    /// Get the current text.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the break opportunities of the current text, as given by
+    /// [`byte_line_breaks`].
+    ///
+    /// [`byte_line_breaks`]: crate::byte_line_breaks
+    #[inline]
+    pub fn breaks(&self) -> &[(usize, Break)] {
+        &self.breaks
+    }
+
+    /// Replace the byte range `range` of the text with `replacement`, and find
+    /// the breaks again where they may have changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or the end of `range` isn't on a `char` boundary,
+    /// or is after the end of the text.
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) -> BreakChanges {
+        let Range { start, end } = range;
+        assert!(
+            start <= end && self.text.is_char_boundary(start) && self.text.is_char_boundary(end),
+            "Invalid range {:?} for a text of {} bytes",
+            start..end,
+            self.text.len()
+        );
+        let restart = self.restart_point(start);
+        self.text.replace_range(start..end, replacement);
+        let new_end = start + replacement.len();
+        // The index in the old text of an index after the edit in the new one.
+        let old_index = |i: usize| i - new_end + end;
+
+        let mut complex = ComplexContext::default();
+        let mut state = self.states[restart] as usize;
+        let mut states = Vec::new();
+        let mut added = Vec::new();
+        let mut position = restart;
+        let mut converged = false;
+        while let Some(c) = self.text[position..].chars().next() {
+            // Past the first `char` after the edit, that `char` is the same as
+            // before, so both texts are in a run of SA or neither is.
+            if position > new_end
+                && state == self.states[old_index(position)] as usize
+                && !self.after_complex_context(position)
+            {
+                converged = true;
+                break;
+            }
+            let next = position + c.len_utf8();
+            let class = self.tailoring.class(c);
+            let rest = self.text[next..].chars();
+            let (new_state, br) = transition(&self.tailoring, state, class, rest);
+            let br = complex.tailor_break(&self.tailoring, &self.text, position, class, br);
+            if br != Break::Prohibited {
+                added.push((position, br));
+            }
+            states.extend((position..next).map(|_| state as u8));
+            state = new_state;
+            position = next;
+        }
+
+        // The breaks from `position` on are the same, or there are none left.
+        let old_position = old_index(position);
+        let first = self.breaks.partition_point(|&(i, _)| i < restart);
+        let last = if converged {
+            self.breaks.partition_point(|&(i, _)| i < old_position)
+        } else {
+            states.push(state as u8);
+            if !self.text.is_empty() {
+                added.push((position, Break::Opportunity));
+            }
+            self.breaks.len()
+        };
+        let mut removed = self
+            .breaks
+            .splice(first..last, added.iter().cloned())
+            .collect::<Vec<_>>();
+        let shift = |i: usize| (i + new_end).wrapping_sub(end);
+        for br in &mut self.breaks[first + added.len()..] {
+            br.0 = shift(br.0);
+        }
+        let state_end = if converged {
+            old_position
+        } else {
+            self.states.len()
+        };
+        self.states.splice(restart..state_end, states);
+
+        // Only report the breaks that aren't in both, at the same place.
+        let moved = |&(i, br): &(usize, Break)| {
+            if i <= start {
+                Some((i, br))
+            } else if i >= end {
+                Some((shift(i), br))
+            } else {
+                None
+            }
+        };
+        // Two old breaks can end up at the same place, when the edit removes
+        // all that was between them, so every new one is only matched once.
+        let mut matched = vec![false; added.len()];
+        removed.retain(|br| {
+            match moved(br).and_then(|br| added.iter().position(|&other| other == br)) {
+                Some(k) if !matched[k] => {
+                    matched[k] = true;
+                    false
+                }
+                _ => true,
+            }
+        });
+        let mut matched = matched.into_iter();
+        added.retain(|_| !matched.next().unwrap());
+        BreakChanges {
+            range: restart..position,
+            removed,
+            added,
+        }
+    }
+
+    // The index to run the algorithm again from, for an edit at `start`.
+    //
+    // The break before an `OP` looks ahead for a `NU` by LB25, skipping
+    // combining marks, so it may depend on the edit. So may the words in a
+    // run of SA characters, which are found by the word segmenter.
+    fn restart_point(&self, start: usize) -> usize {
+        let mut restart = start;
+        for (i, c) in self.text[..start].char_indices().rev() {
+            let class = self.tailoring.class(c);
+            if class == Class::CM || class == Class::ZWJ || self.is_segmented(c) {
+                restart = i;
+            } else {
+                if class == Class::OP {
+                    restart = i;
+                }
+                break;
+            }
+        }
+        restart
+    }
+
+    // Check if the `char` before `position` is in a run of SA characters given
+    // to the word segmenter, which may continue after `position`.
+    fn after_complex_context(&self, position: usize) -> bool {
+        self.text[..position]
+            .chars()
+            .next_back()
+            .is_some_and(|c| self.is_segmented(c))
+    }
+
+    fn is_segmented(&self, c: char) -> bool {
+        self.tailoring.word_segmenter().is_some() && line_break_property(c) == LineBreakProperty::SA
+    }
+}
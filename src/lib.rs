@@ -2,119 +2,244 @@
 //! Algorithm`.
 //!
 //! [UAX #14]: https://www.unicode.org/reports/tr14/
-use std::char;
-use std::iter::Peekable;
-use std::str::Chars;
+//!
+//! # Features
+//!
+//! The crate is `no_std`. The line breaking itself, with iterators like
+//! [`LineBreaks`], [`BreakOpportunities`] and [`Utf16Breaks`], only needs
+//! `core`.
+//!
+//! - `alloc`: everything that allocates, like `byte_line_breaks`, `wrap`,
+//!   `RuleSetBuilder`, `CssOptions`, `explain_line_breaks` and
+//!   `DictionarySegmenter`.
+//! - `std` (default): implies `alloc`, adds `ReadBreaks` for reading from a
+//!   `BufRead`, and lets `DictionarySegmenter` use a `HashSet` instead of a
+//!   `BTreeSet`.
+#![no_std]
+
+#[cfg(feature = "alloc")]
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Peekable};
+use core::str::CharIndices;
+
+mod break_iterator;
+mod chunks;
+mod class;
+mod code_units;
+#[cfg(feature = "alloc")]
+mod css;
+#[cfg(feature = "alloc")]
+mod explain;
+#[cfg(feature = "alloc")]
+mod incremental;
+mod property;
+#[cfg(feature = "std")]
+mod read;
+#[cfg(feature = "alloc")]
+mod rules;
+mod segmenter;
+// Most states are only used by `rules`, to build the tables.
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+mod states;
+mod tailoring;
+mod version;
+#[cfg(feature = "alloc")]
+mod wrap;
+
+use property::is_mark;
+use segmenter::ComplexContext;
+use states::{BREAK_BITS, LB25_OP_STATE, NUM_OF_CLASSES, SOT_STATE};
+
+pub use break_iterator::BreakIterator;
+pub use chunks::{BreakerState, ChunkBreaks};
+pub use class::{Break, Class};
+pub use code_units::{Utf16Breaks, Utf32Breaks, Utf32Unit};
+#[cfg(feature = "alloc")]
+pub use css::{CssOptions, Strictness, WordBreak};
+#[cfg(feature = "alloc")]
+pub use explain::{explain_line_breaks, explain_line_breaks_tailored, Explanation};
+#[cfg(feature = "alloc")]
+pub use incremental::{BreakChanges, IncrementalBreaks};
+pub use property::{line_break_property, LineBreakProperty};
+#[cfg(feature = "std")]
+pub use read::ReadBreaks;
+#[cfg(feature = "alloc")]
+pub use rules::{Rule, RuleSetBuilder, StateTable};
+#[cfg(feature = "alloc")]
+pub use segmenter::{DictionarySegmenter, WordSegmenter};
+#[cfg(feature = "alloc")]
+pub use tailoring::ClassOverrides;
+pub use tailoring::{EastAsianContext, NoTailoring, Tailoring};
+pub use version::UNICODE_VERSION;
+#[cfg(feature = "alloc")]
+pub use wrap::{wrap, wrap_optimal};
 
 /// Convert a `char` to its corresponding [Line Breaking Class].
 ///
+/// This is the value of [`line_break_property`], resolved as by default in
+/// [LB1]: [AI], [SG] and [XX] become [AL], [CJ] becomes [NS], and [SA] becomes
+/// [CM] for combining marks and [AL] for anything else.
+///
 /// *See [`Class`].*
 ///
 /// [Line Breaking Class]: https://www.unicode.org/reports/tr14/#Table1
+/// [LB1]: https://www.unicode.org/reports/tr14/#LB1
+/// [AI]: https://www.unicode.org/reports/tr14/#AI
+/// [SG]: https://www.unicode.org/reports/tr14/#SG
+/// [XX]: https://www.unicode.org/reports/tr14/#XX
+/// [AL]: https://www.unicode.org/reports/tr14/#AL
+/// [CJ]: https://www.unicode.org/reports/tr14/#CJ
+/// [NS]: https://www.unicode.org/reports/tr14/#NS
+/// [SA]: https://www.unicode.org/reports/tr14/#SA
+/// [CM]: https://www.unicode.org/reports/tr14/#CM
+#[inline]
 pub fn convert_to_break_class(n: char) -> Class {
-    // Automatically generated by `../build.rs`
-    include!(concat!(env!("OUT_DIR"), "/convert_to_break_class"))
+    resolve_property(n, line_break_property(n))
 }
 
-/// A [Line Breaking Class].
-///
-/// Interacting directly with Line Breaking Classes is usually not neccessary
-/// unless you want to implement something similar to [`LineBreaks`].
+/// Convert a `char` to its [Line Breaking Class], without resolving [AI].
 ///
-/// For converting `char` to `Class`, see [`convert_to_break_class`].
+/// This is the same as [`convert_to_break_class`], except that ambiguous
+/// characters give `Class::AI` instead of `Class::AL`. Which of `AL` or `ID`
+/// they should be depends on the context, see [`EastAsianContext`].
 ///
-/// Some classes are missing because of [LB1]. These are [SG] (invalid in any
-/// input), [SA] (treated as [CM] or [AL] depending on its General Category),
-/// [CJ] (treated as [NS]), [XX], [SG] and [AI] (all treated as [AL]).
-///
-/// [SG]: https://www.unicode.org/reports/tr14/#SG
-/// [SA]: https://www.unicode.org/reports/tr14/#SA
-/// [CM]: https://www.unicode.org/reports/tr14/#CM
-/// [AL]: https://www.unicode.org/reports/tr14/#AL
-/// [CJ]: https://www.unicode.org/reports/tr14/#CJ
-/// [NS]: https://www.unicode.org/reports/tr14/#NS
-/// [XX]: https://www.unicode.org/reports/tr14/#XX
-/// [SG]: https://www.unicode.org/reports/tr14/#SG
-/// [AI]: https://www.unicode.org/reports/tr14/#AI
-/// [AL]: https://www.unicode.org/reports/tr14/#AL
 /// [Line Breaking Class]: https://www.unicode.org/reports/tr14/#Table1
-/// [LB1]: https://www.unicode.org/reports/tr14/#LB1
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub enum Class {
-    // Non-tailorable Line Breaking Classes
-    BK = 0, // Mandatory Break
-    CR = 1, // Carriage Return
-    LF = 2, // Line Feed
-    CM = 3, // Combining Mark
-    NL = 4, // Next Line
-    // SG,  // Surrogate - Not used
-    WJ = 5,  // Word Joiner
-    ZW = 6,  // Zero Width Space
-    GL = 7,  // Non-breaking ("Glue")
-    SP = 8,  // Space
-    ZWJ = 9, // Zero Width Joiner
-
-    // Break Opportunities
-    B2 = 10, // Break Opportunity Before and After
-    BA = 11, // Break After
-    BB = 12, // Break Before
-    HY = 13, // Hyphen
-    CB = 14, // Contingent Break After
-
-    // Characters Prohibiting Certain Breaks
-    CL = 15, // Clone Punctuation
-    CP = 16, // Close Parenthesis
-    EX = 17, // Exclamation/Interrogation
-    IN = 18, // Inseparable
-    NS = 19, // Nonstarter
-    OP = 20, // Open Punctuation
-    QU = 21, // Quatation
-
-    // Numeric Context
-    IS = 22, // Infix Numeric Separator
-    NU = 23, // Numeric
-    PO = 24, // Postfix Numeric
-    PR = 25, // Prefix Numeric
-    SY = 26, // Symbols Allowing Break After
-
-    // Other Characters
-    // AI, // Ambiguous (Alphabetic or Ideographic) - Not used
-    AL = 27, // Alphabetic
-    // CJ, // Conditional Japanese Starter - Not used
-    EB = 28, // Emoji Base
-    EM = 29, // Emoji Modifier
-    H2 = 30, // Hangul LV Syllable
-    H3 = 31, // Hangul LVT Syllable
-    HL = 32, // Hebrew Letter
-    ID = 33, // Ideographic
-    JL = 34, // Hangul L Jamo
-    JV = 35, // Hangul V Jamo
-    JT = 36, // Hangul T Jamo
-    RI = 37, // Regional Indicator
-    // SA, // Complex Context Dependent (South East Asian) - Not used
-    XX = 38, // Unknown
-}
-
-/// Used by [`LineBreaks`] to specify whether a break is allowed or not.
-///
-/// `Mandatory` is where it is expected to be a line break, `Opportunity` is
-/// where it is allowed to be a line break and `Prohibited` is where a line
-/// break isn't allowed.
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub enum Break {
-    Mandatory,
-    Opportunity,
-    Prohibited,
+/// [AI]: https://www.unicode.org/reports/tr14/#AI
+pub fn original_break_class(n: char) -> Class {
+    match line_break_property(n) {
+        LineBreakProperty::AI => Class::AI,
+        property => resolve_property(n, property),
+    }
 }
 
+// LB1, with the default resolutions of the annex.
+#[inline]
+fn resolve_property(n: char, property: LineBreakProperty) -> Class {
+    match property {
+        LineBreakProperty::AI | LineBreakProperty::SG | LineBreakProperty::XX => Class::AL,
+        LineBreakProperty::CJ => Class::NS,
+        LineBreakProperty::SA if is_mark(n) => Class::CM,
+        LineBreakProperty::SA => Class::AL,
+        LineBreakProperty::BK => Class::BK,
+        LineBreakProperty::CR => Class::CR,
+        LineBreakProperty::LF => Class::LF,
+        LineBreakProperty::CM => Class::CM,
+        LineBreakProperty::NL => Class::NL,
+        LineBreakProperty::WJ => Class::WJ,
+        LineBreakProperty::ZW => Class::ZW,
+        LineBreakProperty::GL => Class::GL,
+        LineBreakProperty::SP => Class::SP,
+        LineBreakProperty::ZWJ => Class::ZWJ,
+        LineBreakProperty::B2 => Class::B2,
+        LineBreakProperty::BA => Class::BA,
+        LineBreakProperty::BB => Class::BB,
+        LineBreakProperty::HY => Class::HY,
+        LineBreakProperty::CB => Class::CB,
+        LineBreakProperty::CL => Class::CL,
+        LineBreakProperty::CP => Class::CP,
+        LineBreakProperty::EX => Class::EX,
+        LineBreakProperty::IN => Class::IN,
+        LineBreakProperty::NS => Class::NS,
+        LineBreakProperty::OP => Class::OP,
+        LineBreakProperty::QU => Class::QU,
+        LineBreakProperty::IS => Class::IS,
+        LineBreakProperty::NU => Class::NU,
+        LineBreakProperty::PO => Class::PO,
+        LineBreakProperty::PR => Class::PR,
+        LineBreakProperty::SY => Class::SY,
+        LineBreakProperty::AL => Class::AL,
+        LineBreakProperty::EB => Class::EB,
+        LineBreakProperty::EM => Class::EM,
+        LineBreakProperty::H2 => Class::H2,
+        LineBreakProperty::H3 => Class::H3,
+        LineBreakProperty::HL => Class::HL,
+        LineBreakProperty::ID => Class::ID,
+        LineBreakProperty::JL => Class::JL,
+        LineBreakProperty::JV => Class::JV,
+        LineBreakProperty::JT => Class::JT,
+        LineBreakProperty::RI => Class::RI,
+    }
+}
+
+// Automatically generated by `../build.rs`, with every cell packed as by
+// `RuleSetBuilder::build`.
 include!(concat!(env!("OUT_DIR"), "/states"));
+include!(concat!(env!("OUT_DIR"), "/ascii_classes"));
+
+/// Split a cell of a state table into the next state and the break before the
+/// `char`.
+#[inline]
+fn unpack(cell: u8) -> (usize, Break) {
+    let br = match cell & ((1 << BREAK_BITS) - 1) {
+        0 => Break::Mandatory,
+        1 => Break::Opportunity,
+        _ => Break::Prohibited,
+    };
+    ((cell >> BREAK_BITS) as usize, br)
+}
+
+/// Get the state machine of `tailoring`.
+#[inline]
+#[cfg(feature = "alloc")]
+fn states<T: Tailoring>(tailoring: &T) -> &[[u8; NUM_OF_CLASSES]] {
+    match tailoring.state_table() {
+        Some(table) => &table.states[..],
+        None => &STATES[..],
+    }
+}
+
+/// Get the state machine of `tailoring`, which is always the built-in one
+/// without `alloc`.
+#[inline]
+#[cfg(not(feature = "alloc"))]
+fn states<T: Tailoring>(_: &T) -> &[[u8; NUM_OF_CLASSES]] {
+    &STATES[..]
+}
+
+/// Find the next state and whether a break is allowed before a `char` of class
+/// `c`, when `rest` is the input following that `char`, classified using
+/// `tailoring`.
+///
+/// The state table can't decide [LB25] by itself for `(PR | PO) × OP`, as it
+/// only holds when the `OP` is followed by a `NU`. The table points those
+/// transitions at `LB25_OP_STATE` and this function looks ahead in `rest`,
+/// skipping combining marks as per [LB9].
+///
+/// [LB25]: https://www.unicode.org/reports/tr14/#LB25
+/// [LB9]: https://www.unicode.org/reports/tr14/#LB9
+fn transition<T, I>(tailoring: &T, state: usize, c: Class, rest: I) -> (usize, Break)
+where
+    T: Tailoring,
+    I: Iterator<Item = char>,
+{
+    let (new_state, br) = unpack(states(tailoring)[state][c as usize]);
+    if new_state == LB25_OP_STATE && state != LB25_OP_STATE {
+        let next = rest
+            .map(|c| tailoring.class(c))
+            .find(|&class| class != Class::CM && class != Class::ZWJ);
+        if next == Some(Class::NU) {
+            return (new_state, Break::Prohibited);
+        }
+    }
+    (new_state, br)
+}
 
 /// An `Iterator` that provides information about possible line breaks in a
 /// `str`.
 ///
 /// As it checks the position after every `char`, it will not give
 /// information about the position before the very first `char`. Luckily that
-/// case is trivial as a line break is never allowed there.
+/// case is trivial as a line break is never allowed there. For the same
+/// reason, an empty `str` gives no items at all.
+///
+/// *See [`LineSegments`] for splitting the input at the break opportunities,
+/// and [`Tailoring`] for customising the algorithm.*
 ///
 /// # Examples
 ///
@@ -135,18 +260,41 @@ include!(concat!(env!("OUT_DIR"), "/states"));
 ///     ["Which ", "is ", "tree? ", "木", "禾", "夫", "🤔", ""]
 /// );
 /// ```
-pub struct LineBreaks<'a> {
-    iter: Peekable<Chars<'a>>,
+pub struct LineBreaks<'a, T = NoTailoring> {
+    input: &'a str,
+    iter: Peekable<CharIndices<'a>>,
     current_state: usize,
+    complex: ComplexContext,
+    tailoring: T,
 }
 
 /// Create a list of all char indices where a line break could be inserted in a
 /// `str`.
 ///
 /// This gives back indices that correspond to `char`s in the original input.
-/// So 0 is before the first `char`, 1 after the first `char` etc.
+/// So 0 is before the first `char`, 1 after the first `char` etc. The last
+/// entry is always the end of the input. An empty input has no `char` to break
+/// after, so it gives an empty list.
+#[cfg(feature = "alloc")]
 pub fn char_line_breaks(input: &str) -> Vec<(usize, Break)> {
-    linebreaks(input, true)
+    char_line_breaks_tailored(input, NoTailoring)
+}
+
+/// Create a list of all char indices where a line break could be inserted in a
+/// `str`, using a [`Tailoring`].
+///
+/// *See [`char_line_breaks`].*
+#[cfg(feature = "alloc")]
+pub fn char_line_breaks_tailored<T: Tailoring>(input: &str, tailoring: T) -> Vec<(usize, Break)> {
+    let mut char_index = 0;
+    let mut byte_index = 0;
+    BreakOpportunities::with_tailoring(input, tailoring)
+        .map(|(i, br)| {
+            char_index += input[byte_index..i].chars().count();
+            byte_index = i;
+            (char_index, br)
+        })
+        .collect()
 }
 
 /// Create a list of all byte indices where a line break could be inserted in a
@@ -154,72 +302,71 @@ pub fn char_line_breaks(input: &str) -> Vec<(usize, Break)> {
 ///
 /// This gives back indices that correspond to bytes in the original input. So
 /// 0 is before the first byte, 1 after the first byte etc. This is useful when
-/// you want to slice a `str` depending on where line breaks are allowed.
+/// you want to slice a `str` depending on where line breaks are allowed. The
+/// last entry is always `input.len()`, unless the input is empty, which gives
+/// an empty list.
+///
+/// *See [`BreakOpportunities`] for a version that doesn't allocate.*
+#[cfg(feature = "alloc")]
 pub fn byte_line_breaks(input: &str) -> Vec<(usize, Break)> {
-    linebreaks(input, false)
-}
-
-fn linebreaks(input: &str, char_indices: bool) -> Vec<(usize, Break)> {
-    let mut current_state = NUM_OF_CLASSES;
-    let mut len = 0;
-    let mut full: Vec<(usize, Break)> = input
-        .char_indices()
-        .map(|(byte_index, ch)| (byte_index, convert_to_break_class(ch)))
-        .enumerate()
-        .filter_map(|(char_index, (byte_index, class))| {
-            let (new_state, break_variant) = STATES[current_state][class as usize];
-            current_state = new_state;
-            len += 1;
-            if break_variant == Break::Prohibited {
-                None
-            } else {
-                if char_indices {
-                    Some((char_index, break_variant))
-                } else {
-                    Some((byte_index, break_variant))
-                }
-            }
-        })
-        .collect();
-    full.push((len, Break::Opportunity));
-    full
+    BreakOpportunities::new(input).collect()
+}
+
+/// Create a list of all byte indices where a line break could be inserted in a
+/// `str`, using a [`Tailoring`].
+///
+/// *See [`byte_line_breaks`].*
+#[cfg(feature = "alloc")]
+pub fn byte_line_breaks_tailored<T: Tailoring>(input: &str, tailoring: T) -> Vec<(usize, Break)> {
+    BreakOpportunities::with_tailoring(input, tailoring).collect()
 }
 
 impl<'a> LineBreaks<'a> {
     /// Construct a `LineBreaks` from a `&str`.
     #[inline]
     pub fn new(input: &'a str) -> LineBreaks<'a> {
-        let mut i = input.chars().peekable();
+        LineBreaks::with_tailoring(input, NoTailoring)
+    }
+}
+
+impl<'a, T: Tailoring> LineBreaks<'a, T> {
+    /// Construct a `LineBreaks` from a `&str`, using a [`Tailoring`].
+    #[inline]
+    pub fn with_tailoring(input: &'a str, tailoring: T) -> LineBreaks<'a, T> {
         let mut out = LineBreaks {
-            iter: i.clone(),
-            current_state: NUM_OF_CLASSES,
+            input,
+            iter: input.char_indices().peekable(),
+            current_state: SOT_STATE,
+            complex: ComplexContext::default(),
+            tailoring,
         };
-        out.possible_break(convert_to_break_class(*i.peek().unwrap()));
+        if let Some(&(i, first)) = out.iter.peek() {
+            out.possible_break(i, first);
+        }
         out
     }
 
-    fn possible_break(&mut self, c: Class) -> Break {
-        let (new_state, br) = STATES[self.current_state][c as usize];
+    fn possible_break(&mut self, i: usize, c: char) -> Break {
+        let class = self.tailoring.class(c);
+        let mut rest = self.iter.clone().map(|(_, c)| c);
+        rest.next();
+        let (new_state, br) = transition(&self.tailoring, self.current_state, class, rest);
         self.current_state = new_state;
-        br
+        self.complex
+            .tailor_break(&self.tailoring, self.input, i, class, br)
     }
 }
 
 /// Provide information as to whether a line break can be appended for each
 /// `char` in the input.
-impl<'a> Iterator for LineBreaks<'a> {
+impl<'a, T: Tailoring> Iterator for LineBreaks<'a, T> {
     type Item = (char, Break);
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
         let tuple = match (self.iter.next(), self.iter.peek()) {
-            (Some(a), Some(&b)) => {
-                (
-                a,
-                self.possible_break(convert_to_break_class(b)),
-            )
-            },
+            (Some((_, a)), Some(&(i, b))) => (a, self.possible_break(i, b)),
             (None, Some(_)) => unreachable!(),
-            (Some(a), None) => (a, Break::Opportunity),
+            (Some((_, a)), None) => (a, Break::Opportunity),
             (None, None) => {
                 return None;
             }
@@ -227,3 +374,221 @@ impl<'a> Iterator for LineBreaks<'a> {
         Some(tuple)
     }
 }
+
+/// An `Iterator` over the byte indices where a line break could be inserted in
+/// a `str`.
+///
+/// This yields the same items as `byte_line_breaks`, but lazily, so it can be
+/// used on large inputs without allocating. Positions where a break is
+/// prohibited are skipped.
+///
+/// *See [`Tailoring`] for customising the algorithm.*
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{Break, BreakOpportunities};
+///
+/// let input = "Price: $(1,234.56)\nDone";
+/// let breaks = BreakOpportunities::new(input).collect::<Vec<_>>();
+/// assert_eq!(
+///     breaks,
+///     [
+///         (7, Break::Opportunity),
+///         (19, Break::Mandatory),
+///         (input.len(), Break::Opportunity)
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct BreakOpportunities<'a, T = NoTailoring> {
+    input: &'a str,
+    // The byte index of the next `char` to check.
+    position: usize,
+    current_state: usize,
+    finished: bool,
+    complex: ComplexContext,
+    tailoring: T,
+}
+
+impl<'a> BreakOpportunities<'a> {
+    /// Construct a `BreakOpportunities` from a `&str`.
+    #[inline]
+    pub fn new(input: &'a str) -> BreakOpportunities<'a> {
+        BreakOpportunities::with_tailoring(input, NoTailoring)
+    }
+}
+
+impl<'a, T: Tailoring> BreakOpportunities<'a, T> {
+    /// Construct a `BreakOpportunities` from a `&str`, using a [`Tailoring`].
+    #[inline]
+    pub fn with_tailoring(input: &'a str, tailoring: T) -> BreakOpportunities<'a, T> {
+        BreakOpportunities {
+            input,
+            position: 0,
+            current_state: SOT_STATE,
+            finished: input.is_empty(),
+            complex: ComplexContext::default(),
+            tailoring,
+        }
+    }
+
+    /// Construct a `BreakOpportunities` that starts at byte index `position`
+    /// of `input`, as if the text started there.
+    pub(crate) fn resume_at(
+        input: &'a str,
+        tailoring: T,
+        position: usize,
+    ) -> BreakOpportunities<'a, T> {
+        BreakOpportunities {
+            position,
+            ..BreakOpportunities::with_tailoring(input, tailoring)
+        }
+    }
+
+    /// Run the ASCII bytes from `position` through the state machine until a
+    /// break opportunity, without decoding UTF-8, if the tailoring has
+    /// [`Tailoring::ascii_classes`].
+    ///
+    /// Stops at the first non-ASCII byte, and before a transition that needs
+    /// the lookahead of LB25, for the general path to continue. ASCII is never
+    /// [SA], so the word segmenter doesn't have to be asked.
+    ///
+    /// [SA]: https://www.unicode.org/reports/tr14/#SA
+    #[inline]
+    fn next_ascii(&mut self) -> Option<(usize, Break)> {
+        let classes = self.tailoring.ascii_classes()?;
+        let states = states(&self.tailoring);
+        let bytes = self.input.as_bytes();
+        let mut position = self.position;
+        let mut state = self.current_state;
+        let mut found = None;
+        while position < bytes.len() && bytes[position].is_ascii() {
+            let class = classes[bytes[position] as usize];
+            let (new_state, br) = unpack(states[state][class as usize]);
+            if new_state == LB25_OP_STATE && state != LB25_OP_STATE {
+                break;
+            }
+            state = new_state;
+            position += 1;
+            if br != Break::Prohibited {
+                found = Some((position - 1, br));
+                break;
+            }
+        }
+        self.position = position;
+        self.current_state = state;
+        found
+    }
+
+    /// Find the next break opportunity before a `char`, which leaves out the
+    /// one at the end of the input.
+    #[inline]
+    fn next_break(&mut self) -> Option<(usize, Break)> {
+        loop {
+            if let Some(item) = self.next_ascii() {
+                return Some(item);
+            }
+            let i = self.position;
+            let c = self.input[i..].chars().next()?;
+            self.position += c.len_utf8();
+            let rest = self.input[self.position..].chars();
+            let class = self.tailoring.class(c);
+            let (new_state, br) = transition(&self.tailoring, self.current_state, class, rest);
+            self.current_state = new_state;
+            let br = self
+                .complex
+                .tailor_break(&self.tailoring, self.input, i, class, br);
+            if br != Break::Prohibited {
+                return Some((i, br));
+            }
+        }
+    }
+}
+
+impl<'a, T: Tailoring> Iterator for BreakOpportunities<'a, T> {
+    type Item = (usize, Break);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.next_break() {
+            return Some(item);
+        }
+        if self.finished {
+            None
+        } else {
+            self.finished = true;
+            Some((self.input.len(), Break::Opportunity))
+        }
+    }
+}
+
+impl<'a, T: Tailoring> FusedIterator for BreakOpportunities<'a, T> {}
+
+/// An `Iterator` over the parts of a `str` between line break opportunities.
+///
+/// Every segment ends where a line break could be inserted, and comes with the
+/// kind of that break. So a segment ending with `Break::Mandatory` has to be
+/// followed by a new line, while the others may be put on the same line as the
+/// next segment. Joining all the segments gives back the input.
+///
+/// *See [`Tailoring`] for customising the algorithm.*
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{Break, LineSegments};
+///
+/// let input = "Which is tree?\n木禾夫🤔";
+/// let segments = LineSegments::new(input).collect::<Vec<_>>();
+/// assert_eq!(
+///     segments,
+///     [
+///         ("Which ", Break::Opportunity),
+///         ("is ", Break::Opportunity),
+///         ("tree?\n", Break::Mandatory),
+///         ("木", Break::Opportunity),
+///         ("禾", Break::Opportunity),
+///         ("夫", Break::Opportunity),
+///         ("🤔", Break::Opportunity),
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct LineSegments<'a, T = NoTailoring> {
+    input: &'a str,
+    breaks: BreakOpportunities<'a, T>,
+    start: usize,
+}
+
+impl<'a> LineSegments<'a> {
+    /// Construct a `LineSegments` from a `&str`.
+    #[inline]
+    pub fn new(input: &'a str) -> LineSegments<'a> {
+        LineSegments::with_tailoring(input, NoTailoring)
+    }
+}
+
+impl<'a, T: Tailoring> LineSegments<'a, T> {
+    /// Construct a `LineSegments` from a `&str`, using a [`Tailoring`].
+    #[inline]
+    pub fn with_tailoring(input: &'a str, tailoring: T) -> LineSegments<'a, T> {
+        LineSegments {
+            input,
+            breaks: BreakOpportunities::with_tailoring(input, tailoring),
+            start: 0,
+        }
+    }
+}
+
+impl<'a, T: Tailoring> Iterator for LineSegments<'a, T> {
+    type Item = (&'a str, Break);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (end, br) = self.breaks.next()?;
+        let segment = &self.input[self.start..end];
+        self.start = end;
+        Some((segment, br))
+    }
+}
+
+impl<'a, T: Tailoring> FusedIterator for LineSegments<'a, T> {}
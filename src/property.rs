@@ -0,0 +1,109 @@
+use core::cmp::Ordering;
+
+/// A value of the [Line_Break] property, as listed in `LineBreak.txt`.
+///
+/// Unlike [`Class`], this has every value of the property, before any
+/// resolution by [LB1]. It is what [`line_break_property`] gives.
+///
+/// [Line_Break]: https://www.unicode.org/reports/tr14/#Table1
+/// [LB1]: https://www.unicode.org/reports/tr14/#LB1
+/// [`Class`]: crate::Class
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum LineBreakProperty {
+    // Non-tailorable Line Breaking Classes
+    BK,  // Mandatory Break
+    CR,  // Carriage Return
+    LF,  // Line Feed
+    CM,  // Combining Mark
+    NL,  // Next Line
+    SG,  // Surrogate
+    WJ,  // Word Joiner
+    ZW,  // Zero Width Space
+    GL,  // Non-breaking ("Glue")
+    SP,  // Space
+    ZWJ, // Zero Width Joiner
+
+    // Break Opportunities
+    B2, // Break Opportunity Before and After
+    BA, // Break After
+    BB, // Break Before
+    HY, // Hyphen
+    CB, // Contingent Break After
+
+    // Characters Prohibiting Certain Breaks
+    CL, // Close Punctuation
+    CP, // Close Parenthesis
+    EX, // Exclamation/Interrogation
+    IN, // Inseparable
+    NS, // Nonstarter
+    OP, // Open Punctuation
+    QU, // Quotation
+
+    // Numeric Context
+    IS, // Infix Numeric Separator
+    NU, // Numeric
+    PO, // Postfix Numeric
+    PR, // Prefix Numeric
+    SY, // Symbols Allowing Break After
+
+    // Other Characters
+    AI, // Ambiguous (Alphabetic or Ideographic)
+    AL, // Alphabetic
+    CJ, // Conditional Japanese Starter
+    EB, // Emoji Base
+    EM, // Emoji Modifier
+    H2, // Hangul LV Syllable
+    H3, // Hangul LVT Syllable
+    HL, // Hebrew Letter
+    ID, // Ideographic
+    JL, // Hangul L Jamo
+    JV, // Hangul V Jamo
+    JT, // Hangul T Jamo
+    RI, // Regional Indicator
+    SA, // Complex Context Dependent (South East Asian)
+    XX, // Unknown
+}
+
+// Automatically generated by `../build.rs`: `BLOCK_BITS`, `PROPERTY_VALUES`,
+// `PROPERTY_INDEX` and `PROPERTY_BLOCKS`.
+include!(concat!(env!("OUT_DIR"), "/line_break_property"));
+include!(concat!(env!("OUT_DIR"), "/marks"));
+
+/// Get the value of the [Line_Break] property of a `char`.
+///
+/// Code points missing from `LineBreak.txt` get the defaults it lists, which is
+/// `XX` apart from some ranges reserved for ideographs and currency symbols.
+///
+/// *See [`convert_to_break_class`] for the Line Breaking Class used by the
+/// algorithm.*
+///
+/// [Line_Break]: https://www.unicode.org/reports/tr14/#Table1
+/// [`convert_to_break_class`]: crate::convert_to_break_class
+#[inline]
+pub fn line_break_property(n: char) -> LineBreakProperty {
+    let n = n as usize;
+    let value = if n < 0x100 {
+        // The blocks of Latin-1 are the first ones, in order.
+        PROPERTY_BLOCKS[n]
+    } else {
+        let block = PROPERTY_INDEX[n >> BLOCK_BITS] as usize;
+        PROPERTY_BLOCKS[(block << BLOCK_BITS) | (n & ((1 << BLOCK_BITS) - 1))]
+    };
+    PROPERTY_VALUES[value as usize]
+}
+
+/// Check if a `char` has the General Category `Mn` or `Mc`.
+pub(crate) fn is_mark(n: char) -> bool {
+    let n = n as u32;
+    MARKS
+        .binary_search_by(|&(left, right)| {
+            if right < n {
+                Ordering::Less
+            } else if left > n {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+use std::str;
+use std::vec::Vec;
+use {
+    line_break_property, Break, BreakerState, ChunkBreaks, LineBreakProperty, NoTailoring,
+    Tailoring,
+};
+
+/// An `Iterator` over the byte indices where a line break could be inserted in
+/// the UTF-8 text of a [`BufRead`].
+///
+/// This yields the same items as [`BreakOpportunities`] on the whole text,
+/// reading it one buffer at a time with [`ChunkBreaks`]. A `char` split between
+/// two buffers is put back together, and a run of [SA] characters at the end of
+/// a buffer is kept until it ends, so that a [`WordSegmenter`] sees all of it.
+///
+/// Invalid UTF-8 gives an error of kind `InvalidData`. After an error, nothing
+/// more is read.
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{Break, ReadBreaks};
+///
+/// let input = "Which is tree?\n木禾".as_bytes();
+/// let breaks = ReadBreaks::new(input)
+///     .collect::<std::io::Result<Vec<_>>>()
+///     .unwrap();
+/// assert_eq!(
+///     breaks,
+///     [
+///         (6, Break::Opportunity),
+///         (9, Break::Opportunity),
+///         (15, Break::Mandatory),
+///         (18, Break::Opportunity),
+///         (21, Break::Opportunity)
+///     ]
+/// );
+/// ```
+///
+/// [`BufRead`]: std::io::BufRead
+/// [`BreakOpportunities`]: crate::BreakOpportunities
+/// [`WordSegmenter`]: crate::WordSegmenter
+/// [SA]: https://www.unicode.org/reports/tr14/#SA
+#[derive(Debug)]
+pub struct ReadBreaks<R, T = NoTailoring> {
+    reader: R,
+    tailoring: T,
+    // What has been read but not broken yet.
+    buffer: Vec<u8>,
+    state: BreakerState,
+    breaks: VecDeque<(usize, Break)>,
+    finished: bool,
+}
+
+impl<R: BufRead> ReadBreaks<R> {
+    /// Construct a `ReadBreaks` from a `BufRead`.
+    #[inline]
+    pub fn new(reader: R) -> ReadBreaks<R> {
+        ReadBreaks::with_tailoring(reader, NoTailoring)
+    }
+}
+
+impl<R: BufRead, T: Tailoring> ReadBreaks<R, T> {
+    /// Construct a `ReadBreaks` from a `BufRead`, using a [`Tailoring`].
+    pub fn with_tailoring(reader: R, tailoring: T) -> ReadBreaks<R, T> {
+        ReadBreaks {
+            reader,
+            tailoring,
+            buffer: Vec::new(),
+            state: BreakerState::new(),
+            breaks: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// Get back the `BufRead`.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    // Read the next buffer and find the breaks in what can be broken of it.
+    fn read_chunk(&mut self) -> io::Result<()> {
+        let read = {
+            let data = self.reader.fill_buf()?;
+            self.buffer.extend_from_slice(data);
+            data.len()
+        };
+        self.reader.consume(read);
+        let end_of_text = read == 0;
+        let valid = match str::from_utf8(&self.buffer) {
+            Ok(text) => text.len(),
+            Err(e) if e.error_len().is_none() && !end_of_text => e.valid_up_to(),
+            Err(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                ))
+            }
+        };
+        let text = str::from_utf8(&self.buffer[..valid]).unwrap();
+        let chunk = if !end_of_text && self.tailoring.word_segmenter().is_some() {
+            text.trim_end_matches(|c| line_break_property(c) == LineBreakProperty::SA)
+        } else {
+            text
+        };
+        let mut breaks = ChunkBreaks::with_tailoring(chunk, self.state, &self.tailoring);
+        if end_of_text {
+            breaks = breaks.end_of_text();
+        }
+        self.breaks.extend(&mut breaks);
+        self.state = breaks.state();
+        let len = chunk.len();
+        self.buffer.drain(..len);
+        self.finished = end_of_text;
+        Ok(())
+    }
+}
+
+impl<R: BufRead, T: Tailoring> Iterator for ReadBreaks<R, T> {
+    type Item = io::Result<(usize, Break)>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.breaks.pop_front() {
+                return Some(Ok(item));
+            } else if self.finished {
+                return None;
+            }
+            match self.read_chunk() {
+                Ok(()) => {}
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
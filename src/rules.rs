@@ -0,0 +1,865 @@
+//! The rules of the annex, as a state machine.
+//!
+//! This module is also included by `build.rs` to generate the built-in
+//! `STATES` table, so it may only use `Break` and `Class` from the crate, the
+//! layout of the state machine from `states`, and the version of Unicode from
+//! `version`.
+use alloc::vec::Vec;
+use core::ops::{Index, IndexMut};
+use states::{
+    BREAK_BITS, LB14_STATE, LB15_STATE, LB16_STATE, LB17_STATE, LB21A_BA_STATE, LB21A_HY_STATE,
+    LB25_CL_STATE, LB25_CP_STATE, LB25_IS_STATE, LB25_OP_STATE, LB25_SY_STATE, LB30A_EVEN_STATE,
+    LB8_STATE, NUM_OF_CLASSES, NUM_OF_STATES, SOT_STATE,
+};
+use version::UNICODE_VERSION;
+use {Break, Class};
+
+const BK: usize = Class::BK as usize;
+const CR: usize = Class::CR as usize;
+const LF: usize = Class::LF as usize;
+const CM: usize = Class::CM as usize;
+const NL: usize = Class::NL as usize;
+const WJ: usize = Class::WJ as usize;
+const ZW: usize = Class::ZW as usize;
+const GL: usize = Class::GL as usize;
+const SP: usize = Class::SP as usize;
+const ZWJ: usize = Class::ZWJ as usize;
+const B2: usize = Class::B2 as usize;
+const BA: usize = Class::BA as usize;
+const BB: usize = Class::BB as usize;
+const HY: usize = Class::HY as usize;
+const CB: usize = Class::CB as usize;
+const CL: usize = Class::CL as usize;
+const CP: usize = Class::CP as usize;
+const EX: usize = Class::EX as usize;
+const IN: usize = Class::IN as usize;
+const NS: usize = Class::NS as usize;
+const OP: usize = Class::OP as usize;
+const QU: usize = Class::QU as usize;
+const IS: usize = Class::IS as usize;
+const NU: usize = Class::NU as usize;
+const PO: usize = Class::PO as usize;
+const PR: usize = Class::PR as usize;
+const SY: usize = Class::SY as usize;
+const AL: usize = Class::AL as usize;
+const EB: usize = Class::EB as usize;
+const EM: usize = Class::EM as usize;
+const H2: usize = Class::H2 as usize;
+const H3: usize = Class::H3 as usize;
+const HL: usize = Class::HL as usize;
+const ID: usize = Class::ID as usize;
+const JL: usize = Class::JL as usize;
+const JV: usize = Class::JV as usize;
+const JT: usize = Class::JT as usize;
+const RI: usize = Class::RI as usize;
+const AI: usize = Class::AI as usize;
+
+const LB9_EXCEPTIONS: [usize; 8] = [BK, CR, LF, NL, SP, ZW, ZWJ, SOT_STATE];
+const LB12A_EXCEPTIONS: [usize; 3] = [SP, BA, HY];
+
+/// Pack the next state and the break before a `char` into a cell of a
+/// `StateTable`.
+fn pack(state: usize, br: Break) -> u8 {
+    ((state as u8) << BREAK_BITS) | br as u8
+}
+
+/// A rule of the [Line Breaking Algorithm].
+///
+/// Used with [`RuleSetBuilder`] to disable rules, and by
+/// [`explain_line_breaks`] to tell which rule decided a break.
+///
+/// LB1, LB3 and LB31 are not part of the state machine, so disabling them has
+/// no effect. LB1 is the resolution of classes, see [`Tailoring`], and is given
+/// for the breaks found by a word segmenter. LB3 always applies, and LB31 is
+/// where no other rule does.
+///
+/// [Line Breaking Algorithm]: https://www.unicode.org/reports/tr14/#Algorithm
+/// [`Tailoring`]: crate::Tailoring
+/// [`explain_line_breaks`]: crate::explain_line_breaks
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Rule {
+    LB1,
+    LB2,
+    LB3,
+    LB4,
+    LB5,
+    LB6,
+    LB7,
+    LB8,
+    LB8a,
+    LB9,
+    LB10,
+    LB11,
+    LB12,
+    LB12a,
+    LB13,
+    LB14,
+    LB15,
+    LB16,
+    LB17,
+    LB18,
+    LB19,
+    LB20,
+    LB21,
+    LB21a,
+    LB21b,
+    LB22,
+    LB23,
+    LB23a,
+    LB24,
+    LB25,
+    LB26,
+    LB27,
+    LB28,
+    LB29,
+    LB30,
+    LB30a,
+    LB30b,
+    LB31,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum CustomRule {
+    Before(Class, Break),
+    After(Class, Break),
+    Between(Class, Class, Break),
+}
+
+// A class that acts as another one, except that some rules of the annex don't
+// apply on one side of it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Relaxed {
+    class: Class,
+    like: Class,
+    rules: &'static [Rule],
+    // Whether the rules are lifted before the class, or else after it.
+    before: bool,
+}
+
+/// The state machine used to find line breaks, as built by a
+/// [`RuleSetBuilder`].
+///
+/// It implements [`Tailoring`], so it can be given directly to [`LineBreaks`]
+/// and the other breaking functions.
+///
+/// [`Tailoring`]: crate::Tailoring
+/// [`LineBreaks`]: crate::LineBreaks
+#[derive(Debug, PartialEq, Clone)]
+pub struct StateTable {
+    pub(crate) states: Vec<[u8; NUM_OF_CLASSES]>,
+    // The rule that decided the break of every cell of `states`, or `None`
+    // for an extra rule.
+    pub(crate) rules: Vec<[Option<Rule>; NUM_OF_CLASSES]>,
+}
+
+/// A builder of a [`StateTable`] with some rules of the annex disabled, or
+/// with extra rules added.
+///
+/// The rules of the annex are applied in the same way as in the built-in table.
+/// Extra rules take precedence over LB13 to LB30b, but not over LB2 to LB12a,
+/// which handle mandatory breaks, spaces, combining marks and glue. A rule for
+/// a class also applies where the annex looks further back, like after the
+/// spaces of `OP SP*` for LB14.
+///
+/// `Class::AI` only reaches the state machine from a tailoring that leaves it
+/// unresolved. It then follows the rules of the annex for `AL`, and the extra
+/// rules given for `AI` itself.
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{byte_line_breaks, byte_line_breaks_tailored, Break, Class, Rule, RuleSetBuilder};
+///
+/// let table = RuleSetBuilder::new()
+///     .disable(Rule::LB30)
+///     .not_allowed_between(Class::ID, Class::ID)
+///     .build();
+///
+/// let input = "f(x)";
+/// assert_eq!(byte_line_breaks(input), [(4, Break::Opportunity)]);
+/// assert_eq!(
+///     byte_line_breaks_tailored(input, &table),
+///     [(1, Break::Opportunity), (4, Break::Opportunity)]
+/// );
+///
+/// let input = "木禾";
+/// assert_eq!(
+///     byte_line_breaks(input),
+///     [(3, Break::Opportunity), (6, Break::Opportunity)]
+/// );
+/// assert_eq!(
+///     byte_line_breaks_tailored(input, &table),
+///     [(6, Break::Opportunity)]
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RuleSetBuilder {
+    disabled: Vec<Rule>,
+    custom: Vec<CustomRule>,
+    relaxed: Vec<Relaxed>,
+}
+
+// A cell of the state machine while it's built: the next state, the break
+// before the `char`, and the rule that decided that break.
+type Cell = (usize, Break, Option<Rule>);
+
+// The state machine while it's built, with the rule being applied, which is
+// recorded in every cell it changes the break of.
+struct Table {
+    states: Vec<[Cell; NUM_OF_CLASSES]>,
+    rule: Option<Rule>,
+}
+
+impl Index<usize> for Table {
+    type Output = [Cell; NUM_OF_CLASSES];
+    fn index(&self, state: usize) -> &[Cell; NUM_OF_CLASSES] {
+        &self.states[state]
+    }
+}
+
+impl IndexMut<usize> for Table {
+    fn index_mut(&mut self, state: usize) -> &mut [Cell; NUM_OF_CLASSES] {
+        &mut self.states[state]
+    }
+}
+
+fn set(cell: &mut Cell, b: Break, rule: Option<Rule>) {
+    cell.1 = b;
+    cell.2 = rule;
+}
+
+// Set the break of a cell of an extra state, unless an extra rule decided it,
+// since those take precedence over the rules of the extra states.
+fn set_unless_extra(cell: &mut Cell, b: Break, rule: Option<Rule>) {
+    if cell.2.is_some() {
+        set(cell, b, rule);
+    }
+}
+
+fn break_before(class: usize, b: Break, states: &mut Table) {
+    let rule = states.rule;
+    for state in states.states.iter_mut() {
+        set(&mut state[class], b, rule);
+    }
+}
+
+fn break_after(state: usize, b: Break, states: &mut Table) {
+    let rule = states.rule;
+    for c in states[state].iter_mut() {
+        set(c, b, rule);
+    }
+}
+
+fn break_between(c1: usize, c2: usize, b: Break, states: &mut Table) {
+    let rule = states.rule;
+    set(&mut states[c1][c2], b, rule);
+}
+
+fn not_allowed_between(c1: usize, c2: usize, states: &mut Table) {
+    break_between(c1, c2, Break::Prohibited, states);
+}
+
+// Apply `relaxed` to the finished state machine. The breaks that its rules
+// decided are left to LB31 instead.
+fn relax(states: &mut [[Cell; NUM_OF_CLASSES]], relaxed: &Relaxed) {
+    let (class, like) = (relaxed.class as usize, relaxed.like as usize);
+    let lifted = |cell: &mut Cell| {
+        if relaxed.rules.iter().any(|&rule| cell.2 == Some(rule)) {
+            set(cell, Break::Opportunity, Some(Rule::LB31));
+        }
+    };
+    for state in states.iter_mut() {
+        let (_, b, rule) = state[like];
+        state[class] = (class, b, rule);
+        if relaxed.before {
+            lifted(&mut state[class]);
+        }
+    }
+    let mut row = states[like];
+    for (i, cell) in row.iter_mut().enumerate() {
+        // Combining marks keep the state of the `char` they're attached to.
+        if cell.0 == like && (i == CM || i == ZWJ) {
+            cell.0 = class;
+        }
+        if !relaxed.before {
+            if cell.0 == LB25_OP_STATE && relaxed.rules.contains(&Rule::LB25) {
+                cell.0 = i;
+            }
+            lifted(cell);
+        }
+    }
+    states[class] = row;
+}
+
+impl RuleSetBuilder {
+    /// Construct a `RuleSetBuilder` with all the rules of the annex.
+    pub fn new() -> RuleSetBuilder {
+        RuleSetBuilder::default()
+    }
+
+    /// Don't apply `rule`.
+    pub fn disable(mut self, rule: Rule) -> RuleSetBuilder {
+        self.disabled.push(rule);
+        self
+    }
+
+    /// Add a rule that sets the break before every `char` of class `class`.
+    pub fn break_before(mut self, class: Class, b: Break) -> RuleSetBuilder {
+        self.custom.push(CustomRule::Before(class, b));
+        self
+    }
+
+    /// Add a rule that sets the break after every `char` of class `class`.
+    pub fn break_after(mut self, class: Class, b: Break) -> RuleSetBuilder {
+        self.custom.push(CustomRule::After(class, b));
+        self
+    }
+
+    /// Add a rule that prohibits breaks between a `char` of class `c1` and a
+    /// following `char` of class `c2`.
+    pub fn not_allowed_between(self, c1: Class, c2: Class) -> RuleSetBuilder {
+        self.break_between(c1, c2, Break::Prohibited)
+    }
+
+    /// Add a rule that sets the break between a `char` of class `c1` and a
+    /// following `char` of class `c2`.
+    pub fn break_between(mut self, c1: Class, c2: Class, b: Break) -> RuleSetBuilder {
+        self.custom.push(CustomRule::Between(c1, c2, b));
+        self
+    }
+
+    // Make `class` act as `like`, except that `rules` don't prohibit breaks
+    // before it, or after it if not `before`. Meant for a class that never
+    // reaches the state machine otherwise, for a tailoring to give to some
+    // `char`s.
+    pub(crate) fn relax(
+        mut self,
+        class: Class,
+        like: Class,
+        rules: &'static [Rule],
+        before: bool,
+    ) -> RuleSetBuilder {
+        self.relaxed.push(Relaxed {
+            class,
+            like,
+            rules,
+            before,
+        });
+        self
+    }
+
+    fn enabled(&self, rule: Rule) -> bool {
+        !self.disabled.contains(&rule)
+    }
+
+    /// Build the `StateTable`, from the rules of the annex as of
+    /// [`UNICODE_VERSION`].
+    ///
+    /// [`UNICODE_VERSION`]: crate::UNICODE_VERSION
+    pub fn build(&self) -> StateTable {
+        let mut states = match UNICODE_VERSION {
+            (11, 0, _) => self.unicode_11(),
+            version => panic!("There are no rules for Unicode {:?}", version),
+        };
+        for relaxed in &self.relaxed {
+            relax(&mut states, relaxed);
+        }
+        assert!(states.len() == NUM_OF_STATES && NUM_OF_STATES <= 1 << (8 - BREAK_BITS));
+        let rules = states
+            .iter()
+            .map(|state| {
+                let mut rules = [None; NUM_OF_CLASSES];
+                for (rule, cell) in rules.iter_mut().zip(state.iter()) {
+                    *rule = cell.2;
+                }
+                rules
+            })
+            .collect();
+        let states = states
+            .iter()
+            .map(|state| {
+                let mut packed = [0; NUM_OF_CLASSES];
+                for (cell, &(next, br, _)) in packed.iter_mut().zip(state.iter()) {
+                    *cell = pack(next, br);
+                }
+                packed
+            })
+            .collect();
+        StateTable { states, rules }
+    }
+
+    // The state machine of Unicode 11.0.0. The rules are applied from the last
+    // to the first, so that the earlier ones take precedence.
+    fn unicode_11(&self) -> Vec<[Cell; NUM_OF_CLASSES]> {
+        let mut states = Table {
+            states: Vec::new(),
+            rule: None,
+        };
+        let mut extra_states: Vec<[Cell; NUM_OF_CLASSES]> = Vec::new();
+
+        let mut initial = [(0, Break::Opportunity, Some(Rule::LB31)); NUM_OF_CLASSES];
+        for (i, c) in initial.iter_mut().enumerate() {
+            c.0 = i;
+        }
+        for _ in 0..(NUM_OF_CLASSES + 1) {
+            states.states.push(initial);
+        }
+
+        // LB30b
+        if self.enabled(Rule::LB30b) {
+            states.rule = Some(Rule::LB30b);
+            not_allowed_between(EB, EM, &mut states);
+        }
+
+        // LB30a
+        if self.enabled(Rule::LB30a) {
+            states.rule = Some(Rule::LB30a);
+            not_allowed_between(RI, RI, &mut states);
+            states[RI][RI].0 = LB30A_EVEN_STATE;
+        }
+
+        // LB30
+        if self.enabled(Rule::LB30) {
+            states.rule = Some(Rule::LB30);
+            not_allowed_between(AL, OP, &mut states);
+            not_allowed_between(HL, OP, &mut states);
+            not_allowed_between(NU, OP, &mut states);
+
+            not_allowed_between(CP, AL, &mut states);
+            not_allowed_between(CP, HL, &mut states);
+            not_allowed_between(CP, NU, &mut states);
+        }
+
+        // LB29
+        if self.enabled(Rule::LB29) {
+            states.rule = Some(Rule::LB29);
+            not_allowed_between(IS, AL, &mut states);
+            not_allowed_between(IS, HL, &mut states);
+        }
+
+        // LB28
+        if self.enabled(Rule::LB28) {
+            states.rule = Some(Rule::LB28);
+            not_allowed_between(AL, AL, &mut states);
+            not_allowed_between(AL, HL, &mut states);
+            not_allowed_between(HL, AL, &mut states);
+            not_allowed_between(HL, HL, &mut states);
+        }
+
+        // LB27
+        if self.enabled(Rule::LB27) {
+            states.rule = Some(Rule::LB27);
+            not_allowed_between(JL, IN, &mut states);
+            not_allowed_between(JV, IN, &mut states);
+            not_allowed_between(JT, IN, &mut states);
+            not_allowed_between(H2, IN, &mut states);
+            not_allowed_between(H3, IN, &mut states);
+
+            not_allowed_between(JL, PO, &mut states);
+            not_allowed_between(JV, PO, &mut states);
+            not_allowed_between(JT, PO, &mut states);
+            not_allowed_between(H2, PO, &mut states);
+            not_allowed_between(H3, PO, &mut states);
+
+            not_allowed_between(PR, JL, &mut states);
+            not_allowed_between(PR, JV, &mut states);
+            not_allowed_between(PR, JT, &mut states);
+            not_allowed_between(PR, H2, &mut states);
+            not_allowed_between(PR, H3, &mut states);
+        }
+
+        // LB26
+        if self.enabled(Rule::LB26) {
+            states.rule = Some(Rule::LB26);
+            not_allowed_between(JL, JL, &mut states);
+            not_allowed_between(JL, JV, &mut states);
+            not_allowed_between(JL, H2, &mut states);
+            not_allowed_between(JL, H3, &mut states);
+
+            not_allowed_between(JV, JV, &mut states);
+            not_allowed_between(JV, JT, &mut states);
+            not_allowed_between(H2, JV, &mut states);
+            not_allowed_between(H2, JT, &mut states);
+
+            not_allowed_between(JT, JT, &mut states);
+            not_allowed_between(H3, JT, &mut states);
+        }
+
+        // LB25
+        if self.enabled(Rule::LB25) {
+            states.rule = Some(Rule::LB25);
+            // Implemented as the regular expression
+            // (PR | PO)? (OP | HY)? NU (NU | SY | IS)* (CL | CP)? (PR | PO)?
+            // instead of the pair table. The `(NU | SY | IS)* (CL | CP)?` part is
+            // tracked by the extra LB25 states, and `(PR | PO) × OP` needs to look
+            // ahead for a `NU`, which `LB25_OP_STATE` tells the caller to do.
+            not_allowed_between(NU, PO, &mut states);
+            not_allowed_between(NU, PR, &mut states);
+            not_allowed_between(PO, NU, &mut states);
+            not_allowed_between(PR, NU, &mut states);
+            not_allowed_between(HY, NU, &mut states);
+            not_allowed_between(NU, NU, &mut states);
+
+            states[NU][SY].0 = LB25_SY_STATE;
+            states[NU][IS].0 = LB25_IS_STATE;
+            states[NU][CL].0 = LB25_CL_STATE;
+            states[NU][CP].0 = LB25_CP_STATE;
+            states[PR][OP].0 = LB25_OP_STATE;
+            states[PO][OP].0 = LB25_OP_STATE;
+        }
+
+        // LB24
+        if self.enabled(Rule::LB24) {
+            states.rule = Some(Rule::LB24);
+            not_allowed_between(PR, AL, &mut states);
+            not_allowed_between(PR, HL, &mut states);
+            not_allowed_between(PO, AL, &mut states);
+            not_allowed_between(PO, HL, &mut states);
+            not_allowed_between(AL, PR, &mut states);
+            not_allowed_between(AL, PO, &mut states);
+            not_allowed_between(HL, PR, &mut states);
+            not_allowed_between(HL, PO, &mut states);
+        }
+
+        // LB23a
+        if self.enabled(Rule::LB23a) {
+            states.rule = Some(Rule::LB23a);
+            not_allowed_between(PR, ID, &mut states);
+            not_allowed_between(PR, EB, &mut states);
+            not_allowed_between(PR, EM, &mut states);
+            not_allowed_between(ID, PO, &mut states);
+            not_allowed_between(EB, PO, &mut states);
+            not_allowed_between(EM, PO, &mut states);
+        }
+
+        // LB23
+        if self.enabled(Rule::LB23) {
+            states.rule = Some(Rule::LB23);
+            not_allowed_between(AL, NU, &mut states);
+            not_allowed_between(HL, NU, &mut states);
+            not_allowed_between(NU, AL, &mut states);
+            not_allowed_between(NU, HL, &mut states);
+        }
+
+        // LB22
+        if self.enabled(Rule::LB22) {
+            states.rule = Some(Rule::LB22);
+            not_allowed_between(AL, IN, &mut states);
+            not_allowed_between(HL, IN, &mut states);
+            not_allowed_between(EX, IN, &mut states);
+            not_allowed_between(ID, IN, &mut states);
+            not_allowed_between(EB, IN, &mut states);
+            not_allowed_between(EM, IN, &mut states);
+            not_allowed_between(IN, IN, &mut states);
+            not_allowed_between(NU, IN, &mut states);
+        }
+
+        // LB21b
+        if self.enabled(Rule::LB21b) {
+            states.rule = Some(Rule::LB21b);
+            not_allowed_between(SY, HL, &mut states);
+        }
+
+        // LB21a
+        if self.enabled(Rule::LB21a) {
+            states.rule = Some(Rule::LB21a);
+            states[HL][HY].0 = LB21A_HY_STATE;
+            states[HL][BA].0 = LB21A_BA_STATE;
+        }
+
+        // LB21
+        if self.enabled(Rule::LB21) {
+            states.rule = Some(Rule::LB21);
+            break_before(BA, Break::Prohibited, &mut states);
+            break_before(HY, Break::Prohibited, &mut states);
+            break_before(NS, Break::Prohibited, &mut states);
+            break_after(BB, Break::Prohibited, &mut states);
+        }
+
+        // LB20
+        if self.enabled(Rule::LB20) {
+            states.rule = Some(Rule::LB20);
+            break_before(CB, Break::Opportunity, &mut states);
+            break_after(CB, Break::Opportunity, &mut states);
+        }
+
+        // LB19
+        if self.enabled(Rule::LB19) {
+            states.rule = Some(Rule::LB19);
+            break_before(QU, Break::Prohibited, &mut states);
+            break_after(QU, Break::Prohibited, &mut states);
+        }
+
+        // LB18
+        if self.enabled(Rule::LB18) {
+            states.rule = Some(Rule::LB18);
+            break_after(SP, Break::Opportunity, &mut states);
+        }
+
+        // LB17
+        if self.enabled(Rule::LB17) {
+            states.rule = Some(Rule::LB17);
+            not_allowed_between(B2, B2, &mut states);
+            states[B2][SP].0 = LB17_STATE;
+        }
+
+        // LB16
+        if self.enabled(Rule::LB16) {
+            states.rule = Some(Rule::LB16);
+            not_allowed_between(CL, NS, &mut states);
+            states[CL][SP].0 = LB16_STATE;
+
+            not_allowed_between(CP, NS, &mut states);
+            states[CP][SP].0 = LB16_STATE;
+        }
+
+        // LB15
+        if self.enabled(Rule::LB15) {
+            states.rule = Some(Rule::LB15);
+            not_allowed_between(QU, OP, &mut states);
+            states[QU][SP].0 = LB15_STATE;
+        }
+
+        // LB14
+        if self.enabled(Rule::LB14) {
+            states.rule = Some(Rule::LB14);
+            break_after(OP, Break::Prohibited, &mut states);
+            states[OP][SP].0 = LB14_STATE;
+        }
+
+        // LB13
+        if self.enabled(Rule::LB13) {
+            states.rule = Some(Rule::LB13);
+            break_before(CL, Break::Prohibited, &mut states);
+            break_before(CP, Break::Prohibited, &mut states);
+            break_before(EX, Break::Prohibited, &mut states);
+            break_before(IS, Break::Prohibited, &mut states);
+            break_before(SY, Break::Prohibited, &mut states);
+        }
+
+        // AI is resolved before reaching the state machine, so it only gets
+        // here from a tailoring that leaves it. It then acts as AL, like the
+        // default resolution of LB1, except for the extra rules.
+        for state in states.states.iter_mut() {
+            state[AI] = (AI, state[AL].1, state[AL].2);
+        }
+        states[AI] = states[AL];
+
+        states.rule = None;
+        for rule in &self.custom {
+            match *rule {
+                CustomRule::Before(class, b) => break_before(class as usize, b, &mut states),
+                CustomRule::After(class, b) => break_after(class as usize, b, &mut states),
+                CustomRule::Between(c1, c2, b) => {
+                    break_between(c1 as usize, c2 as usize, b, &mut states)
+                }
+            }
+        }
+
+        // LB12a
+        if self.enabled(Rule::LB12a) {
+            states.rule = Some(Rule::LB12a);
+            for state in states
+                .states
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(index, state)| {
+                    if LB12A_EXCEPTIONS.contains(&index) {
+                        None
+                    } else {
+                        Some(state)
+                    }
+                })
+            {
+                set(&mut state[GL], Break::Prohibited, Some(Rule::LB12a));
+            }
+        }
+
+        // LB12
+        if self.enabled(Rule::LB12) {
+            states.rule = Some(Rule::LB12);
+            break_after(GL, Break::Prohibited, &mut states);
+        }
+
+        // LB11
+        if self.enabled(Rule::LB11) {
+            states.rule = Some(Rule::LB11);
+            break_after(WJ, Break::Prohibited, &mut states);
+            break_before(WJ, Break::Prohibited, &mut states);
+        }
+
+        // LB10
+        if self.enabled(Rule::LB10) {
+            states.rule = Some(Rule::LB10);
+            not_allowed_between(AL, CM, &mut states);
+            not_allowed_between(AL, ZWJ, &mut states);
+
+            states[CM] = states[AL];
+            states[ZWJ] = states[AL];
+        }
+
+        // LB9
+        if self.enabled(Rule::LB9) {
+            states.rule = Some(Rule::LB9);
+            for (i, state) in states
+                .states
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(index, state)| {
+                    if LB9_EXCEPTIONS.contains(&index) {
+                        None
+                    } else {
+                        Some((index, state))
+                    }
+                })
+            {
+                state[CM] = (i, Break::Prohibited, Some(Rule::LB9));
+                state[ZWJ] = (i, Break::Prohibited, Some(Rule::LB9));
+            }
+        }
+
+        // LB8a
+        if self.enabled(Rule::LB8a) {
+            states.rule = Some(Rule::LB8a);
+            break_after(ZWJ, Break::Prohibited, &mut states);
+        }
+
+        // LB8
+        if self.enabled(Rule::LB8) {
+            states.rule = Some(Rule::LB8);
+            break_after(ZW, Break::Opportunity, &mut states);
+            states[ZW][SP].0 = LB8_STATE;
+        }
+
+        // LB7
+        if self.enabled(Rule::LB7) {
+            states.rule = Some(Rule::LB7);
+            break_before(SP, Break::Prohibited, &mut states);
+            break_before(ZW, Break::Prohibited, &mut states);
+        }
+
+        // LB6
+        if self.enabled(Rule::LB6) {
+            states.rule = Some(Rule::LB6);
+            break_before(BK, Break::Prohibited, &mut states);
+            break_before(CR, Break::Prohibited, &mut states);
+            break_before(LF, Break::Prohibited, &mut states);
+            break_before(NL, Break::Prohibited, &mut states);
+        }
+
+        // LB5
+        if self.enabled(Rule::LB5) {
+            states.rule = Some(Rule::LB5);
+            break_after(CR, Break::Mandatory, &mut states);
+            break_after(LF, Break::Mandatory, &mut states);
+            break_after(NL, Break::Mandatory, &mut states);
+            not_allowed_between(CR, LF, &mut states);
+        }
+
+        // LB4
+        if self.enabled(Rule::LB4) {
+            states.rule = Some(Rule::LB4);
+            break_after(BK, Break::Mandatory, &mut states);
+        }
+
+        // LB2
+        if self.enabled(Rule::LB2) {
+            states.rule = Some(Rule::LB2);
+            break_after(SOT_STATE, Break::Prohibited, &mut states);
+        }
+
+        // Special extra states. They are added even if their rule is disabled, so
+        // that the numbering of the states stays the same.
+
+        // LB8
+        let mut new_state = states[SP];
+        for part in new_state.iter_mut().enumerate().filter_map(|(i, s)| {
+            if [BK, CR, LF, NL, SP, ZW].contains(&i) {
+                None
+            } else {
+                Some(s)
+            }
+        }) {
+            set(part, Break::Opportunity, Some(Rule::LB8));
+        }
+        extra_states.push(new_state);
+
+        // LB14. The breaks already prohibited after a space are so by an
+        // earlier rule, which keeps them. The same goes for LB21a. From here
+        // on, the extra rules for the class of the copied state are kept too.
+        let mut new_state = states[SP];
+        for part in new_state.iter_mut() {
+            if part.1 != Break::Prohibited {
+                set_unless_extra(part, Break::Prohibited, Some(Rule::LB14));
+            }
+        }
+        extra_states.push(new_state);
+
+        // LB15
+        let mut new_state = states[SP];
+        set_unless_extra(&mut new_state[OP], Break::Prohibited, Some(Rule::LB15));
+        extra_states.push(new_state);
+
+        // LB16
+        let mut new_state = states[SP];
+        set_unless_extra(&mut new_state[NS], Break::Prohibited, Some(Rule::LB16));
+        extra_states.push(new_state);
+
+        // LB17
+        let mut new_state = states[SP];
+        set_unless_extra(&mut new_state[B2], Break::Prohibited, Some(Rule::LB17));
+        extra_states.push(new_state);
+
+        // LB21a
+        let mut hy_state = states[HY];
+        for part in hy_state.iter_mut() {
+            if part.1 != Break::Prohibited {
+                set_unless_extra(part, Break::Prohibited, Some(Rule::LB21a));
+            }
+        }
+        let mut ba_state = states[BA];
+        for part in ba_state.iter_mut() {
+            if part.1 != Break::Prohibited {
+                set_unless_extra(part, Break::Prohibited, Some(Rule::LB21a));
+            }
+        }
+        extra_states.push(hy_state);
+        extra_states.push(ba_state);
+
+        // LB30a
+        let mut even_state = states[RI];
+        even_state[RI].0 = RI;
+        set_unless_extra(&mut even_state[RI], Break::Opportunity, Some(Rule::LB30a));
+        extra_states.push(even_state);
+
+        // LB25
+        let number_state = |class: usize, state: usize| {
+            let mut new_state = states[class];
+            if self.enabled(Rule::LB9) {
+                new_state[CM].0 = state;
+                new_state[ZWJ].0 = state;
+            }
+            new_state
+        };
+        for &(class, state) in [(SY, LB25_SY_STATE), (IS, LB25_IS_STATE)].iter() {
+            let mut new_state = number_state(class, state);
+            set_unless_extra(&mut new_state[NU], Break::Prohibited, Some(Rule::LB25));
+            new_state[SY].0 = LB25_SY_STATE;
+            new_state[IS].0 = LB25_IS_STATE;
+            new_state[CL].0 = LB25_CL_STATE;
+            new_state[CP].0 = LB25_CP_STATE;
+            extra_states.push(new_state);
+        }
+        for &(class, state) in [(CL, LB25_CL_STATE), (CP, LB25_CP_STATE)].iter() {
+            let mut new_state = number_state(class, state);
+            set_unless_extra(&mut new_state[PO], Break::Prohibited, Some(Rule::LB25));
+            set_unless_extra(&mut new_state[PR], Break::Prohibited, Some(Rule::LB25));
+            extra_states.push(new_state);
+        }
+        extra_states.push(number_state(OP, LB25_OP_STATE));
+
+        let mut states = states.states;
+        states.extend(extra_states);
+        states
+    }
+}
@@ -0,0 +1,361 @@
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::collections::BTreeSet as Set;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashSet as Set;
+#[cfg(feature = "alloc")]
+use {convert_to_break_class, line_break_property, LineBreakProperty, ASCII_CLASSES};
+use {Break, Class, Tailoring};
+
+/// Check if a `char` is of the [SA] Line Breaking Class, used by scripts like
+/// Thai, Lao, Khmer and Myanmar that don't separate words with spaces.
+///
+/// [SA]: https://www.unicode.org/reports/tr14/#SA
+#[cfg(feature = "alloc")]
+fn is_complex_context(n: char) -> bool {
+    line_break_property(n) == LineBreakProperty::SA
+}
+
+/// A way to find the words in a run of [SA] text.
+///
+/// The annex leaves breaking [SA] text to "a dictionary or other appropriate
+/// mechanism", as these scripts don't mark the boundaries between words. Without
+/// one, every run of [SA] characters is treated as a single word. A
+/// `WordSegmenter` is used by returning it from
+/// [`Tailoring::word_segmenter`].
+///
+/// *See [`DictionarySegmenter`] for a segmenter using a list of words.*
+///
+/// [SA]: https://www.unicode.org/reports/tr14/#SA
+#[cfg(feature = "alloc")]
+pub trait WordSegmenter {
+    /// Push the byte indices of `run` where a new word starts onto `breaks`,
+    /// in increasing order.
+    ///
+    /// `run` is a maximal run of [SA] characters. The start and the end of the
+    /// run are handled by the rest of the algorithm, so `0` and `run.len()`
+    /// should not be included. A break opportunity is only given before a
+    /// combining mark (`CM`) if the rest of the algorithm allows it.
+    ///
+    /// [SA]: https://www.unicode.org/reports/tr14/#SA
+    fn segment(&self, run: &str, breaks: &mut Vec<usize>);
+}
+
+#[cfg(feature = "alloc")]
+impl<T: WordSegmenter + ?Sized> WordSegmenter for &T {
+    #[inline]
+    fn segment(&self, run: &str, breaks: &mut Vec<usize>) {
+        (**self).segment(run, breaks)
+    }
+}
+
+#[cfg(feature = "alloc")]
+static THAI_WORDS: &str = include_str!("../dictionaries/th.txt");
+
+/// A [`WordSegmenter`] that splits text into the longest words found in a
+/// dictionary.
+///
+/// Starting from the beginning of a run, the longest word of the dictionary
+/// that the text starts with is taken, and the search continues after it. Text
+/// that doesn't start with any word is kept together until the next word. A
+/// word is not allowed to end in front of a combining mark.
+///
+/// It also implements [`Tailoring`], so it can be given directly to
+/// [`LineBreaks`] and the other iterators.
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{byte_line_breaks, byte_line_breaks_tailored, Break, DictionarySegmenter};
+///
+/// let input = "ภาษาไทยง่ายนิดเดียว";
+/// assert_eq!(byte_line_breaks(input), [(input.len(), Break::Opportunity)]);
+///
+/// let thai = DictionarySegmenter::thai();
+/// let words = byte_line_breaks_tailored(input, &thai)
+///     .into_iter()
+///     .scan(0, |start, (end, _)| Some(&input[std::mem::replace(start, end)..end]))
+///     .collect::<Vec<_>>();
+/// assert_eq!(words, ["ภาษา", "ไทย", "ง่าย", "นิด", "เดียว"]);
+/// ```
+///
+/// [`LineBreaks`]: crate::LineBreaks
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct DictionarySegmenter {
+    words: Set<String>,
+    // The length of the longest word, in bytes.
+    longest: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl DictionarySegmenter {
+    /// Construct a `DictionarySegmenter` from a list of words.
+    pub fn new<I>(words: I) -> DictionarySegmenter
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let words: Set<String> = words
+            .into_iter()
+            .map(Into::into)
+            .filter(|word: &String| !word.is_empty())
+            .collect();
+        let longest = words.iter().map(|word| word.len()).max().unwrap_or(0);
+        DictionarySegmenter { words, longest }
+    }
+
+    /// Construct a `DictionarySegmenter` for Thai, using the bundled dictionary
+    /// of about 26000 words from [ICU].
+    ///
+    /// Building the dictionary takes a moment, so it's best to construct it
+    /// once and reuse it.
+    ///
+    /// [ICU]: https://icu.unicode.org/
+    pub fn thai() -> DictionarySegmenter {
+        DictionarySegmenter::new(THAI_WORDS.lines().filter(|line| !line.starts_with('#')))
+    }
+
+    // The length in bytes of the longest word that `text` starts with.
+    fn longest_word(&self, text: &str) -> Option<usize> {
+        let mut ends = text
+            .char_indices()
+            .skip(1)
+            .map(|(i, _)| i)
+            .chain(Some(text.len()));
+        let mut found = None;
+        let mut end = ends.next();
+        while let Some(i) = end {
+            if i > self.longest {
+                break;
+            }
+            end = ends.next();
+            let before_mark = end.is_some()
+                && convert_to_break_class(text[i..].chars().next().unwrap()) == Class::CM;
+            if !before_mark && self.words.contains(&text[..i]) {
+                found = Some(i);
+            }
+        }
+        found
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl WordSegmenter for DictionarySegmenter {
+    fn segment(&self, run: &str, breaks: &mut Vec<usize>) {
+        let mut start = 0;
+        let mut in_unknown = false;
+        while let Some(c) = run[start..].chars().next() {
+            match self.longest_word(&run[start..]) {
+                Some(len) => {
+                    if start > 0 {
+                        breaks.push(start);
+                    }
+                    start += len;
+                    in_unknown = false;
+                }
+                None => {
+                    if start > 0 && !in_unknown {
+                        breaks.push(start);
+                    }
+                    start += c.len_utf8();
+                    in_unknown = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Tailoring for DictionarySegmenter {
+    #[inline]
+    fn word_segmenter(&self) -> Option<&dyn WordSegmenter> {
+        Some(self)
+    }
+
+    #[inline]
+    fn ascii_classes(&self) -> Option<&[Class; 128]> {
+        Some(&ASCII_CLASSES)
+    }
+}
+
+/// The word boundaries found by a [`WordSegmenter`] in the current run of [SA]
+/// text, for the iterators to check each position against.
+///
+/// [SA]: https://www.unicode.org/reports/tr14/#SA
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ComplexContext {
+    breaks: Vec<usize>,
+    next: usize,
+    run_end: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl ComplexContext {
+    /// Find the break before the `char` at byte index `i` of `input`, of class
+    /// `class`, when the rest of the algorithm gives `br`.
+    ///
+    /// A prohibited break becomes an opportunity where the word segmenter of
+    /// `tailoring` finds the start of a word, unless it's before a combining
+    /// mark. Has to be called for every `char` of the input, in order.
+    #[inline]
+    pub(crate) fn tailor_break<T>(
+        &mut self,
+        tailoring: &T,
+        input: &str,
+        i: usize,
+        class: Class,
+        br: Break,
+    ) -> Break
+    where
+        T: Tailoring,
+    {
+        self.tailor(tailoring, i, class, br, |segmenter, breaks| {
+            let run = &input[i..];
+            if !run.chars().next().is_some_and(is_complex_context) {
+                return None;
+            }
+            let len = run.find(|c| !is_complex_context(c)).unwrap_or(run.len());
+            segmenter.segment(&run[..len], breaks);
+            for br in breaks.iter_mut() {
+                *br += i;
+            }
+            Some(i + len)
+        })
+    }
+
+    /// Like `tailor_break`, for an input that isn't a `str`, where `rest`
+    /// gives the `char`s from index `i` on with their indices, and `len` is the
+    /// length of the input.
+    ///
+    /// The runs of [SA] characters are collected into a `String` for the word
+    /// segmenter, and its breaks are mapped back to the indices of `rest`.
+    ///
+    /// [SA]: https://www.unicode.org/reports/tr14/#SA
+    #[inline]
+    pub(crate) fn tailor_break_chars<T, I>(
+        &mut self,
+        tailoring: &T,
+        rest: I,
+        len: usize,
+        i: usize,
+        class: Class,
+        br: Break,
+    ) -> Break
+    where
+        T: Tailoring,
+        I: Iterator<Item = (usize, char)>,
+    {
+        self.tailor(tailoring, i, class, br, |segmenter, breaks| {
+            let mut run = String::new();
+            // The byte index in `run` and the index in the input of every
+            // `char` of the run.
+            let mut indices = Vec::new();
+            let mut run_end = len;
+            for (j, c) in rest {
+                if !is_complex_context(c) {
+                    run_end = j;
+                    break;
+                }
+                indices.push((run.len(), j));
+                run.push(c);
+            }
+            if run.is_empty() {
+                return None;
+            }
+            segmenter.segment(&run, breaks);
+            for br in breaks.iter_mut() {
+                let k = indices
+                    .binary_search_by_key(br, |&(byte, _)| byte)
+                    .unwrap_or_else(|k| k);
+                *br = indices.get(k).map_or(run_end, |&(_, j)| j);
+            }
+            Some(run_end)
+        })
+    }
+
+    // Where a run of SA characters starts at `i`, `segment_run` pushes the
+    // starts of its words onto the `Vec` and gives the end of the run.
+    #[inline]
+    fn tailor<T, F>(
+        &mut self,
+        tailoring: &T,
+        i: usize,
+        class: Class,
+        br: Break,
+        segment_run: F,
+    ) -> Break
+    where
+        T: Tailoring,
+        F: FnOnce(&dyn WordSegmenter, &mut Vec<usize>) -> Option<usize>,
+    {
+        let segmenter = match tailoring.word_segmenter() {
+            Some(segmenter) => segmenter,
+            None => return br,
+        };
+        if self.is_boundary(segmenter, i, segment_run)
+            && br == Break::Prohibited
+            && class != Class::CM
+            && class != Class::ZWJ
+        {
+            Break::Opportunity
+        } else {
+            br
+        }
+    }
+
+    fn is_boundary<F>(&mut self, segmenter: &dyn WordSegmenter, i: usize, segment_run: F) -> bool
+    where
+        F: FnOnce(&dyn WordSegmenter, &mut Vec<usize>) -> Option<usize>,
+    {
+        if i >= self.run_end {
+            self.breaks.clear();
+            self.next = 0;
+            if let Some(run_end) = segment_run(segmenter, &mut self.breaks) {
+                self.run_end = run_end;
+            }
+            return false;
+        }
+        while self.next < self.breaks.len() && self.breaks[self.next] < i {
+            self.next += 1;
+        }
+        self.next < self.breaks.len() && self.breaks[self.next] == i
+    }
+}
+
+/// Without `alloc` there are no word segmenters, so the breaks are kept.
+#[cfg(not(feature = "alloc"))]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ComplexContext {}
+
+#[cfg(not(feature = "alloc"))]
+impl ComplexContext {
+    #[inline]
+    pub(crate) fn tailor_break<T>(&mut self, _: &T, _: &str, _: usize, _: Class, br: Break) -> Break
+    where
+        T: Tailoring,
+    {
+        br
+    }
+
+    #[inline]
+    pub(crate) fn tailor_break_chars<T, I>(
+        &mut self,
+        _: &T,
+        _: I,
+        _: usize,
+        _: usize,
+        _: Class,
+        br: Break,
+    ) -> Break
+    where
+        T: Tailoring,
+        I: Iterator<Item = (usize, char)>,
+    {
+        br
+    }
+}
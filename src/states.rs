@@ -0,0 +1,31 @@
+//! The layout of the state machine, shared by the rules that build it and the
+//! iterators that run it.
+//!
+//! This module is also included by `build.rs`.
+
+// The first states are the ones after a `char` of each class.
+pub(crate) const NUM_OF_CLASSES: usize = 40;
+
+// The state at the start of the input.
+pub(crate) const SOT_STATE: usize = NUM_OF_CLASSES;
+pub(crate) const LB8_STATE: usize = NUM_OF_CLASSES + 1;
+pub(crate) const LB14_STATE: usize = NUM_OF_CLASSES + 2;
+pub(crate) const LB15_STATE: usize = NUM_OF_CLASSES + 3;
+pub(crate) const LB16_STATE: usize = NUM_OF_CLASSES + 4;
+pub(crate) const LB17_STATE: usize = NUM_OF_CLASSES + 5;
+pub(crate) const LB21A_HY_STATE: usize = NUM_OF_CLASSES + 6;
+pub(crate) const LB21A_BA_STATE: usize = NUM_OF_CLASSES + 7;
+pub(crate) const LB30A_EVEN_STATE: usize = NUM_OF_CLASSES + 8;
+pub(crate) const LB25_SY_STATE: usize = NUM_OF_CLASSES + 9;
+pub(crate) const LB25_IS_STATE: usize = NUM_OF_CLASSES + 10;
+pub(crate) const LB25_CL_STATE: usize = NUM_OF_CLASSES + 11;
+pub(crate) const LB25_CP_STATE: usize = NUM_OF_CLASSES + 12;
+// Entered on `(PR | PO) OP`, where the break before the `OP` depends on
+// whether a `NU` follows.
+pub(crate) const LB25_OP_STATE: usize = NUM_OF_CLASSES + 13;
+
+pub(crate) const NUM_OF_STATES: usize = LB25_OP_STATE + 1;
+
+// A cell of a state table packs the next state above the `Break` before the
+// `char`, which takes the two lowest bits.
+pub(crate) const BREAK_BITS: u32 = 2;
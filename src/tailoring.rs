@@ -0,0 +1,249 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::ops::RangeInclusive;
+use {convert_to_break_class, original_break_class, Class, ASCII_CLASSES};
+#[cfg(feature = "alloc")]
+use {StateTable, WordSegmenter};
+
+/// Customisation of the line breaking algorithm, as allowed by [Tailoring].
+///
+/// Every method has a default that follows the annex, so implementing the
+/// trait only requires overriding what should be different. It's accepted by
+/// [`LineBreaks`], [`BreakOpportunities`], [`LineSegments`] and the `_tailored`
+/// versions of `char_line_breaks` and `byte_line_breaks`.
+///
+/// [Tailoring]: https://www.unicode.org/reports/tr14/#Tailoring
+/// [`LineBreaks`]: crate::LineBreaks
+/// [`BreakOpportunities`]: crate::BreakOpportunities
+/// [`LineSegments`]: crate::LineSegments
+pub trait Tailoring {
+    /// Get the Line Breaking Class to use for `c`.
+    ///
+    /// Defaults to [`convert_to_break_class`].
+    #[inline]
+    fn class(&self, c: char) -> Class {
+        convert_to_break_class(c)
+    }
+
+    /// Get the state machine to use, or `None` for the rules of the annex.
+    ///
+    /// *See [`RuleSetBuilder`] for building one.*
+    ///
+    /// [`RuleSetBuilder`]: crate::RuleSetBuilder
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn state_table(&self) -> Option<&StateTable> {
+        None
+    }
+
+    /// Get the segmenter to find break opportunities inside runs of [SA]
+    /// characters, or `None` to never break inside them.
+    ///
+    /// *See [`DictionarySegmenter`] for a segmenter that can be used.*
+    ///
+    /// [SA]: https://www.unicode.org/reports/tr14/#SA
+    /// [`DictionarySegmenter`]: crate::DictionarySegmenter
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn word_segmenter(&self) -> Option<&dyn WordSegmenter> {
+        None
+    }
+
+    /// Get the Line Breaking Classes that `class` gives to the ASCII
+    /// characters, indexed by their code, or `None` to always call `class`.
+    ///
+    /// With a table, runs of ASCII are classified without decoding UTF-8,
+    /// which is much faster. Defaults to `None`, as the table has to agree
+    /// with `class`.
+    #[inline]
+    fn ascii_classes(&self) -> Option<&[Class; 128]> {
+        None
+    }
+}
+
+impl<T: Tailoring + ?Sized> Tailoring for &T {
+    #[inline]
+    fn class(&self, c: char) -> Class {
+        (**self).class(c)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn state_table(&self) -> Option<&StateTable> {
+        (**self).state_table()
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn word_segmenter(&self) -> Option<&dyn WordSegmenter> {
+        (**self).word_segmenter()
+    }
+
+    #[inline]
+    fn ascii_classes(&self) -> Option<&[Class; 128]> {
+        (**self).ascii_classes()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Tailoring for StateTable {
+    #[inline]
+    fn state_table(&self) -> Option<&StateTable> {
+        Some(self)
+    }
+
+    #[inline]
+    fn ascii_classes(&self) -> Option<&[Class; 128]> {
+        Some(&ASCII_CLASSES)
+    }
+}
+
+/// The algorithm exactly as described by the annex, without any tailoring.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoTailoring;
+
+impl Tailoring for NoTailoring {
+    #[inline]
+    fn ascii_classes(&self) -> Option<&[Class; 128]> {
+        Some(&ASCII_CLASSES)
+    }
+}
+
+/// A [`Tailoring`] that changes the Line Breaking Class of some code points.
+///
+/// Code points without an override keep the class from
+/// [`convert_to_break_class`]. When overrides overlap, the one added last is
+/// used.
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{byte_line_breaks, byte_line_breaks_tailored, Break, Class, ClassOverrides};
+///
+/// // Never break after U+2010 HYPHEN, by treating it as glue.
+/// let tailoring = ClassOverrides::new().set('\u{2010}', Class::GL);
+/// let input = "co\u{2010}op";
+/// assert_eq!(
+///     byte_line_breaks(input),
+///     [(5, Break::Opportunity), (input.len(), Break::Opportunity)]
+/// );
+/// assert_eq!(
+///     byte_line_breaks_tailored(input, &tailoring),
+///     [(input.len(), Break::Opportunity)]
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClassOverrides {
+    overrides: Vec<(char, char, Class)>,
+}
+
+#[cfg(feature = "alloc")]
+impl ClassOverrides {
+    /// Construct a `ClassOverrides` without any overrides.
+    pub fn new() -> ClassOverrides {
+        ClassOverrides::default()
+    }
+
+    /// Use `class` for the code point `c`.
+    pub fn set(self, c: char, class: Class) -> ClassOverrides {
+        self.set_range(c..=c, class)
+    }
+
+    /// Use `class` for every code point in `range`.
+    pub fn set_range(mut self, range: RangeInclusive<char>, class: Class) -> ClassOverrides {
+        self.overrides.push((*range.start(), *range.end(), class));
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Tailoring for ClassOverrides {
+    #[inline]
+    fn class(&self, c: char) -> Class {
+        self.overrides
+            .iter()
+            .rev()
+            .find(|&&(start, end, _)| start <= c && c <= end)
+            .map(|&(_, _, class)| class)
+            .unwrap_or_else(|| convert_to_break_class(c))
+    }
+}
+
+/// A [`Tailoring`] that resolves ambiguous characters ([AI]) according to
+/// whether the text is East Asian.
+///
+/// Ambiguous characters, like circled numbers and some punctuation, are
+/// wide in East Asian typography and should then break like ideographs
+/// ([ID]). Elsewhere they break like letters ([AL]), which is what
+/// [`convert_to_break_class`] and `NoTailoring` do.
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{byte_line_breaks_tailored, Break, EastAsianContext};
+///
+/// let input = "①②";
+/// let context = EastAsianContext::from_locale("ja-JP");
+/// assert_eq!(context, EastAsianContext::EastAsian);
+/// assert_eq!(
+///     byte_line_breaks_tailored(input, context),
+///     [(3, Break::Opportunity), (6, Break::Opportunity)]
+/// );
+/// assert_eq!(
+///     byte_line_breaks_tailored(input, EastAsianContext::from_locale("en")),
+///     [(6, Break::Opportunity)]
+/// );
+/// ```
+///
+/// [AI]: https://www.unicode.org/reports/tr14/#AI
+/// [ID]: https://www.unicode.org/reports/tr14/#ID
+/// [AL]: https://www.unicode.org/reports/tr14/#AL
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EastAsianContext {
+    /// Ambiguous characters are resolved to `AL`.
+    #[default]
+    Other,
+    /// Ambiguous characters are resolved to `ID`.
+    EastAsian,
+}
+
+impl EastAsianContext {
+    /// Get the context of text in the language of the [BCP 47] language tag
+    /// `locale`, like `"zh-Hant"` or `"en_US"`.
+    ///
+    /// Chinese, Japanese and Korean are East Asian.
+    ///
+    /// [BCP 47]: https://www.rfc-editor.org/info/bcp47
+    pub fn from_locale(locale: &str) -> EastAsianContext {
+        let language = locale.split(['-', '_']).next().unwrap_or("");
+        let east_asian = ["zh", "ja", "ko", "yue", "cmn", "wuu", "hak", "nan"];
+        if east_asian.iter().any(|l| l.eq_ignore_ascii_case(language)) {
+            EastAsianContext::EastAsian
+        } else {
+            EastAsianContext::Other
+        }
+    }
+
+    /// Resolve `class`, turning `Class::AI` into `Class::ID` or `Class::AL`.
+    pub fn resolve(self, class: Class) -> Class {
+        match (class, self) {
+            (Class::AI, EastAsianContext::EastAsian) => Class::ID,
+            (Class::AI, EastAsianContext::Other) => Class::AL,
+            (class, _) => class,
+        }
+    }
+}
+
+impl Tailoring for EastAsianContext {
+    #[inline]
+    fn class(&self, c: char) -> Class {
+        self.resolve(original_break_class(c))
+    }
+
+    #[inline]
+    fn ascii_classes(&self) -> Option<&[Class; 128]> {
+        Some(&ASCII_CLASSES)
+    }
+}
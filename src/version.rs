@@ -0,0 +1,30 @@
+//! The version of Unicode selected by the `unicode-*` features, which picks
+//! both the data files and the rules of the state machine.
+//!
+//! This module is also included by `build.rs`.
+
+// Every supported version of Unicode, from oldest to newest, together with
+// whether its cargo feature is enabled. A version needs its data files and
+// `LineBreakTest.txt` in `unicode-data/`, its rules in `RuleSetBuilder::build`
+// and its test data in `tests/full.rs`.
+const UNICODE_VERSIONS: [(bool, (u8, u8, u8)); 1] = [(cfg!(feature = "unicode-11"), (11, 0, 0))];
+
+/// The version of Unicode that the line breaking data and rules are from, as
+/// `(major, minor, update)`.
+///
+/// The version is chosen at build time with the `unicode-*` cargo features. If
+/// several are enabled, the newest one is used.
+pub const UNICODE_VERSION: (u8, u8, u8) = newest_enabled();
+
+// The newest version whose feature is enabled, or the newest version overall if
+// no `unicode-*` feature is enabled.
+const fn newest_enabled() -> (u8, u8, u8) {
+    let mut i = UNICODE_VERSIONS.len();
+    while i > 0 {
+        i -= 1;
+        if UNICODE_VERSIONS[i].0 {
+            return UNICODE_VERSIONS[i].1;
+        }
+    }
+    UNICODE_VERSIONS[UNICODE_VERSIONS.len() - 1].1
+}
@@ -0,0 +1,231 @@
+use alloc::vec::Vec;
+use core::ops::Add;
+use {convert_to_break_class, Break, Class, LineSegments};
+
+/// Split a `str` into lines no wider than `max_width`, breaking greedily.
+///
+/// Each line is filled with as many segments of [`LineSegments`] as fit, and a
+/// new line is always started after a `Break::Mandatory`. The width of a piece
+/// of text is given by `measure`, which can count `char`s, bytes, display
+/// columns or the advance of a font, as long as the width of two consecutive
+/// pieces is the sum of their widths. A segment that is wider than `max_width`
+/// by itself is put on its own line without being split.
+///
+/// Spaces at the end of a line are not counted towards its width and are
+/// removed from the returned lines, together with any characters causing a
+/// mandatory break, like `'\n'`.
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::wrap;
+///
+/// let input = "The quick (\"brown\") fox can't jump 32.3 feet, right?\nNo.";
+/// let lines = wrap(input, 20, |s: &str| s.chars().count());
+/// assert_eq!(
+///     lines,
+///     [
+///         "The quick (\"brown\")",
+///         "fox can't jump 32.3",
+///         "feet, right?",
+///         "No."
+///     ]
+/// );
+/// ```
+pub fn wrap<F, W>(input: &str, max_width: W, mut measure: F) -> Vec<&str>
+where
+    F: FnMut(&str) -> W,
+    W: Copy + Default + PartialOrd + Add<Output = W>,
+{
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    let mut line_end = 0;
+    let mut width = W::default();
+    let mut segment_start = 0;
+    for (segment, br) in LineSegments::new(input) {
+        let content = trim_end(segment);
+        let content_width = measure(content);
+        if segment_start > line_start && width + content_width > max_width {
+            lines.push(&input[line_start..line_end]);
+            line_start = segment_start;
+            width = W::default();
+        }
+        line_end = segment_start + content.len();
+        segment_start += segment.len();
+        if br == Break::Mandatory {
+            lines.push(&input[line_start..line_end]);
+            line_start = segment_start;
+            width = W::default();
+        } else {
+            width = width + content_width + measure(&segment[content.len()..]);
+        }
+    }
+    if line_start < input.len() {
+        lines.push(&input[line_start..line_end]);
+    }
+    lines
+}
+
+// Remove trailing spaces and characters causing a mandatory break.
+fn trim_end(segment: &str) -> &str {
+    segment.trim_end_matches(|c| {
+        matches!(
+            convert_to_break_class(c),
+            Class::SP | Class::BK | Class::CR | Class::LF | Class::NL
+        )
+    })
+}
+
+// Demerits added to every line, so that fewer lines are preferred.
+const LINE_PENALTY: f64 = 10.0;
+// Penalty for ending a line after a hyphen (`HY`) or a `BA` character.
+const HYPHEN_PENALTY: f64 = 50.0;
+// Badness of a line that doesn't fit, used when a single segment is too wide.
+const OVERFULL_BADNESS: f64 = 10000.0;
+
+struct Segment {
+    start: usize,
+    content_end: usize,
+    mandatory: bool,
+    penalty: f64,
+    content_width: f64,
+    width: f64,
+}
+
+/// Split a `str` into lines, minimising the raggedness of the whole paragraph.
+///
+/// Where [`wrap`] fills every line as far as possible, this chooses among all
+/// the break opportunities at once, in the manner of the Knuth–Plass
+/// algorithm, so that the lines of a paragraph end up evenly filled. A new
+/// line is always started after a `Break::Mandatory`.
+///
+/// Line `n` may be at most `line_widths[n]` wide, and lines after the end of
+/// `line_widths` use its last width. Widths are given by `measure` in any unit,
+/// with the same assumption as [`wrap`] that widths can be summed.
+///
+/// Every line gets demerits in the same way as in TeX: `(10 + b)² + p²`,
+/// where `b` is the badness `100 · (unused width / line width)³` and `p` is a
+/// penalty of 50 for ending the line after a hyphen (`HY`) or a break-after
+/// character (`BA`). The last line of a paragraph, or a line ending in a
+/// mandatory break, has badness 0. Lines never overflow, unless a single
+/// segment is too wide, which then gets its own line.
+///
+/// The returned lines have trailing spaces and mandatory break characters
+/// removed, like in [`wrap`].
+///
+/// # Panics
+///
+/// Panics if `line_widths` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use uax_14::{wrap, wrap_optimal};
+///
+/// let input = "aaa bb cc ddddd";
+/// let chars = |s: &str| s.chars().count();
+/// assert_eq!(wrap(input, 6, chars), ["aaa bb", "cc", "ddddd"]);
+/// assert_eq!(
+///     wrap_optimal(input, &[6.0], |s: &str| chars(s) as f64),
+///     ["aaa", "bb cc", "ddddd"]
+/// );
+/// ```
+pub fn wrap_optimal<'a, F>(input: &'a str, line_widths: &[f64], mut measure: F) -> Vec<&'a str>
+where
+    F: FnMut(&str) -> f64,
+{
+    assert!(!line_widths.is_empty(), "no line widths were given");
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for (segment, br) in LineSegments::new(input) {
+        let content = trim_end(segment);
+        let penalty = match content.chars().next_back().map(convert_to_break_class) {
+            Some(Class::HY) | Some(Class::BA) => HYPHEN_PENALTY,
+            _ => 0.0,
+        };
+        let content_width = measure(content);
+        segments.push(Segment {
+            start,
+            content_end: start + content.len(),
+            mandatory: br == Break::Mandatory,
+            penalty,
+            content_width,
+            width: content_width + measure(&segment[content.len()..]),
+        });
+        start += segment.len();
+    }
+
+    // `best[j][l]` is the lowest total demerits for putting the first `j`
+    // segments on lines, with the next line using `line_widths[l]`, together
+    // with where the previous line started. Lines beyond the end of
+    // `line_widths` all share the last index.
+    let last_width = line_widths.len() - 1;
+    let widest = line_widths.iter().cloned().fold(0.0, f64::max);
+    let mut best = vec![vec![None; line_widths.len()]; segments.len() + 1];
+    best[0][0] = Some((0.0, 0, 0));
+    // A line can't contain a mandatory break, so it never starts before this.
+    let mut paragraph_start = 0;
+    for j in 1..=segments.len() {
+        let last = &segments[j - 1];
+        let ends_paragraph = last.mandatory || j == segments.len();
+        let penalty = if ends_paragraph { 0.0 } else { last.penalty };
+        let mut width = last.content_width;
+        for i in (paragraph_start..j).rev() {
+            if i < j - 1 {
+                width += segments[i].width;
+            }
+            for l in 0..line_widths.len() {
+                let previous = match best[i][l] {
+                    Some((demerits, _, _)) => demerits,
+                    None => continue,
+                };
+                let max_width = line_widths[l];
+                let badness = if width > max_width {
+                    if i < j - 1 {
+                        continue;
+                    }
+                    OVERFULL_BADNESS
+                } else if ends_paragraph {
+                    0.0
+                } else {
+                    // `powi` isn't available without `std`.
+                    let slack = (max_width - width) / max_width;
+                    (100.0 * slack * slack * slack).min(OVERFULL_BADNESS)
+                };
+                let line = LINE_PENALTY + badness;
+                let demerits = previous + line * line + penalty * penalty;
+                let next = (l + 1).min(last_width);
+                match best[j][next] {
+                    Some((d, _, _)) if d <= demerits => {}
+                    _ => best[j][next] = Some((demerits, i, l)),
+                }
+            }
+            // Starting the line earlier only makes it wider.
+            if width > widest {
+                break;
+            }
+        }
+        if last.mandatory {
+            paragraph_start = j;
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut j = segments.len();
+    let mut l = (0..line_widths.len())
+        .filter(|&l| best[j][l].is_some())
+        .min_by(|&a, &b| {
+            let a = best[j][a].unwrap().0;
+            let b = best[j][b].unwrap().0;
+            a.partial_cmp(&b).unwrap()
+        })
+        .unwrap_or(0);
+    while j > 0 {
+        let (_, i, previous_l) = best[j][l].unwrap();
+        lines.push(&input[segments[i].start..segments[j - 1].content_end]);
+        j = i;
+        l = previous_l;
+    }
+    lines.reverse();
+    lines
+}
@@ -0,0 +1,801 @@
+extern crate uax_14;
+
+use std::io::{self, BufReader};
+use uax_14::{
+    byte_line_breaks, byte_line_breaks_tailored, char_line_breaks, char_line_breaks_tailored,
+    convert_to_break_class, explain_line_breaks, explain_line_breaks_tailored, line_break_property,
+    original_break_class, wrap, wrap_optimal, Break, BreakIterator, BreakOpportunities,
+    BreakerState, ChunkBreaks, Class, ClassOverrides, CssOptions, DictionarySegmenter,
+    EastAsianContext, IncrementalBreaks, LineBreakProperty, LineBreaks, LineSegments, NoTailoring,
+    ReadBreaks, Rule, RuleSetBuilder, Strictness, Tailoring, Utf16Breaks, Utf32Breaks, WordBreak,
+    WordSegmenter,
+};
+
+#[test]
+fn empty_input() {
+    assert_eq!(LineBreaks::new("").next(), None);
+    assert_eq!(char_line_breaks(""), []);
+    assert_eq!(byte_line_breaks(""), []);
+}
+
+#[test]
+fn single_char() {
+    assert_eq!(
+        LineBreaks::new("a").collect::<Vec<_>>(),
+        [('a', Break::Opportunity)]
+    );
+    assert_eq!(char_line_breaks("a"), [(1, Break::Opportunity)]);
+    assert_eq!(byte_line_breaks("a"), [(1, Break::Opportunity)]);
+}
+
+#[test]
+fn end_of_text() {
+    let input = "木禾 ö";
+    assert_eq!(
+        char_line_breaks(input).last(),
+        Some(&(4, Break::Opportunity))
+    );
+    assert_eq!(
+        byte_line_breaks(input).last(),
+        Some(&(input.len(), Break::Opportunity))
+    );
+}
+
+#[test]
+fn wrap_mandatory_breaks() {
+    let chars = |s: &str| s.chars().count();
+    assert_eq!(wrap("a\r\nb\n\nc", 10, chars), ["a", "b", "", "c"]);
+    assert_eq!(wrap("a\n", 10, chars), ["a"]);
+    assert_eq!(wrap("", 10, chars), Vec::<&str>::new());
+}
+
+#[test]
+fn wrap_trailing_spaces() {
+    let chars = |s: &str| s.chars().count();
+    assert_eq!(wrap("aaa   bbb", 3, chars), ["aaa", "bbb"]);
+    assert_eq!(wrap("aa bb  \ncc", 5, chars), ["aa bb", "cc"]);
+}
+
+#[test]
+fn wrap_overflow() {
+    let chars = |s: &str| s.chars().count();
+    assert_eq!(wrap("a bbbbbb c", 3, chars), ["a", "bbbbbb", "c"]);
+    assert_eq!(
+        wrap("木禾夫", 2.0, |s: &str| s.chars().count() as f32),
+        ["木禾", "夫"]
+    );
+}
+
+#[test]
+fn wrap_optimal_line_widths() {
+    let chars = |s: &str| s.chars().count() as f64;
+    assert_eq!(
+        wrap_optimal("aa bb cc dd ee ff", &[2.0, 5.0], chars),
+        ["aa", "bb cc", "dd ee", "ff"]
+    );
+    assert_eq!(
+        wrap_optimal("aa bb\n\ncc-dd ee", &[8.0], chars),
+        ["aa bb", "", "cc-dd ee"]
+    );
+    assert_eq!(wrap_optimal("", &[8.0], chars), Vec::<&str>::new());
+    assert_eq!(
+        wrap_optimal("aaaaaaaa bb", &[4.0], chars),
+        ["aaaaaaaa", "bb"]
+    );
+}
+
+#[test]
+fn class_overrides() {
+    let tailoring = ClassOverrides::new()
+        .set_range('a'..='z', Class::ID)
+        .set('x', Class::AL);
+    assert_eq!(tailoring.class('a'), Class::ID);
+    assert_eq!(tailoring.class('x'), Class::AL);
+    assert_eq!(tailoring.class('A'), Class::AL);
+    assert_eq!(
+        char_line_breaks_tailored("abxx", &tailoring),
+        [
+            (1, Break::Opportunity),
+            (2, Break::Opportunity),
+            (4, Break::Opportunity)
+        ]
+    );
+}
+
+#[test]
+fn custom_tailoring() {
+    struct SpacesAreGlue;
+    impl Tailoring for SpacesAreGlue {
+        fn class(&self, c: char) -> Class {
+            match c {
+                ' ' => Class::GL,
+                _ => uax_14::convert_to_break_class(c),
+            }
+        }
+    }
+    let breaks = LineBreaks::with_tailoring("a b", SpacesAreGlue).collect::<Vec<_>>();
+    assert_eq!(
+        breaks,
+        [
+            ('a', Break::Prohibited),
+            (' ', Break::Prohibited),
+            ('b', Break::Opportunity)
+        ]
+    );
+}
+
+#[test]
+fn rule_set_builder() {
+    fn breaks(input: &str, c1: Class, c2: Class) -> Vec<usize> {
+        let table = RuleSetBuilder::new()
+            .break_between(c1, c2, Break::Opportunity)
+            .build();
+        char_line_breaks_tailored(input, &table)
+            .iter()
+            .map(|&(i, _)| i)
+            .collect()
+    }
+    // The extra rules apply after the context of LB14, LB21a and LB25 too.
+    assert_eq!(breaks("( a", Class::SP, Class::AL), [2, 3]);
+    assert_eq!(breaks("a-1", Class::HY, Class::NU), [2, 3]);
+    assert_eq!(breaks("א-1", Class::HY, Class::NU), [2, 3]);
+    assert_eq!(breaks("a)%", Class::CP, Class::PO), [2, 3]);
+    assert_eq!(breaks("(1)%", Class::CP, Class::PO), [3, 4]);
+
+    // The extra rules for `AI` aren't replaced by those for `AL`.
+    struct Unresolved(uax_14::StateTable);
+    impl Tailoring for Unresolved {
+        fn class(&self, c: char) -> Class {
+            original_break_class(c)
+        }
+        fn state_table(&self) -> Option<&uax_14::StateTable> {
+            Some(&self.0)
+        }
+    }
+    let table = RuleSetBuilder::new()
+        .not_allowed_between(Class::ID, Class::AI)
+        .break_after(Class::AI, Break::Opportunity)
+        .build();
+    assert_eq!(
+        char_line_breaks_tailored("木①a", Unresolved(table)),
+        [(2, Break::Opportunity), (3, Break::Opportunity)]
+    );
+}
+
+#[test]
+fn dictionary_segmenter() {
+    let thai = DictionarySegmenter::thai();
+    let input = "ฉันกินข้าว (ภาษาไทย)";
+    assert_eq!(
+        char_line_breaks_tailored(input, &thai),
+        [
+            (3, Break::Opportunity),
+            (6, Break::Opportunity),
+            (11, Break::Opportunity),
+            (16, Break::Opportunity),
+            (20, Break::Opportunity)
+        ]
+    );
+    let from_chars = LineBreaks::with_tailoring(input, &thai)
+        .scan(0, |i, (c, br)| {
+            *i += c.len_utf8();
+            Some((*i, br))
+        })
+        .filter(|&(_, br)| br != Break::Prohibited)
+        .collect::<Vec<_>>();
+    assert_eq!(from_chars, byte_line_breaks_tailored(input, &thai));
+
+    // Unknown text is kept together, and words never end before a mark.
+    let words = DictionarySegmenter::new(vec!["กา", "ขา"]);
+    assert_eq!(
+        char_line_breaks_tailored("กาฅฆขาก\u{E34}", &words),
+        [
+            (2, Break::Opportunity),
+            (4, Break::Opportunity),
+            (6, Break::Opportunity),
+            (8, Break::Opportunity)
+        ]
+    );
+}
+
+#[test]
+fn custom_word_segmenter() {
+    // Break before every `char` that isn't a combining mark.
+    struct EveryChar;
+
+    impl WordSegmenter for EveryChar {
+        fn segment(&self, run: &str, breaks: &mut Vec<usize>) {
+            breaks.extend(run.char_indices().skip(1).map(|(i, _)| i));
+        }
+    }
+
+    impl Tailoring for EveryChar {
+        fn word_segmenter(&self) -> Option<&dyn WordSegmenter> {
+            Some(self)
+        }
+    }
+
+    let input = "กขี";
+    assert_eq!(byte_line_breaks(input), [(input.len(), Break::Opportunity)]);
+    assert_eq!(
+        byte_line_breaks_tailored(input, EveryChar),
+        [(3, Break::Opportunity), (input.len(), Break::Opportunity)]
+    );
+}
+
+#[test]
+fn css_line_break() {
+    let strict = CssOptions::new().chinese_or_japanese(true);
+    let normal = strict.clone().line_break(Strictness::Normal);
+    let loose = strict.clone().line_break(Strictness::Loose);
+
+    // Prolonged sound mark and wave dash
+    let input = "ケーキ〜";
+    assert_eq!(
+        char_line_breaks_tailored(input, &strict),
+        [(2, Break::Opportunity), (4, Break::Opportunity)]
+    );
+    assert_eq!(
+        char_line_breaks_tailored(input, &normal),
+        [
+            (1, Break::Opportunity),
+            (2, Break::Opportunity),
+            (3, Break::Opportunity),
+            (4, Break::Opportunity)
+        ]
+    );
+    // Breaks before hyphens are only allowed for Chinese and Japanese.
+    let other = CssOptions::new().line_break(Strictness::Normal);
+    assert_eq!(
+        char_line_breaks_tailored(input, &other),
+        [
+            (1, Break::Opportunity),
+            (2, Break::Opportunity),
+            (4, Break::Opportunity)
+        ]
+    );
+
+    // Inseparable characters, centered punctuation and wide suffixes
+    let input = "あ‥‥い・100％";
+    assert_eq!(
+        char_line_breaks_tailored(input, &normal),
+        [
+            (3, Break::Opportunity),
+            (5, Break::Opportunity),
+            (9, Break::Opportunity)
+        ]
+    );
+    assert_eq!(
+        char_line_breaks_tailored(input, &loose),
+        [
+            (2, Break::Opportunity),
+            (3, Break::Opportunity),
+            (4, Break::Opportunity),
+            (5, Break::Opportunity),
+            (8, Break::Opportunity),
+            (9, Break::Opportunity)
+        ]
+    );
+
+    // Wide prefixes can only be broken after, and wide postfixes before.
+    assert_eq!(
+        char_line_breaks_tailored("abc￥100", &strict),
+        [(7, Break::Opportunity)]
+    );
+    assert_eq!(
+        char_line_breaks_tailored("abc￥100", &loose),
+        [(4, Break::Opportunity), (7, Break::Opportunity)]
+    );
+    assert_eq!(
+        char_line_breaks_tailored("100％a", &strict),
+        [(5, Break::Opportunity)]
+    );
+    assert_eq!(
+        char_line_breaks_tailored("100％a", &loose),
+        [(3, Break::Opportunity), (5, Break::Opportunity)]
+    );
+    // Only for Chinese and Japanese, whichever option is set last.
+    let other = CssOptions::new().line_break(Strictness::Loose);
+    assert_eq!(
+        char_line_breaks_tailored("100％a", &other),
+        [(5, Break::Opportunity)]
+    );
+    assert_eq!(
+        char_line_breaks_tailored("100％a", other.chinese_or_japanese(true)),
+        [(3, Break::Opportunity), (5, Break::Opportunity)]
+    );
+}
+
+#[test]
+fn css_line_break_anywhere() {
+    let anywhere = CssOptions::new().line_break(Strictness::Anywhere);
+    assert_eq!(
+        char_line_breaks_tailored("a-e\u{301}\u{A0}c\n🇸🇪", &anywhere),
+        [
+            (1, Break::Opportunity),
+            (2, Break::Opportunity),
+            (4, Break::Opportunity),
+            (5, Break::Opportunity),
+            (7, Break::Mandatory),
+            (9, Break::Opportunity)
+        ]
+    );
+}
+
+#[test]
+fn css_word_break() {
+    fn segments<'a>(input: &'a str, options: &CssOptions) -> Vec<&'a str> {
+        LineSegments::with_tailoring(input, options)
+            .map(|(segment, _)| segment)
+            .collect()
+    }
+    let normal = CssOptions::new();
+    let break_all = CssOptions::new().word_break(WordBreak::BreakAll);
+    let keep_all = CssOptions::new().word_break(WordBreak::KeepAll);
+
+    assert_eq!(segments("Ab 12", &normal), ["Ab ", "12"]);
+    assert_eq!(segments("Ab 12", &break_all), ["A", "b ", "1", "2"]);
+    assert_eq!(segments("Ab 12", &keep_all), ["Ab ", "12"]);
+
+    // Ideographs and kana are kept together, but not punctuation.
+    let input = "日本語のテキスト、です。";
+    assert_eq!(segments(input, &keep_all), ["日本語のテキスト、", "です。"]);
+    assert_eq!(segments(input, &normal).len(), 10);
+
+    // Korean is only broken at spaces.
+    let input = "그 사람은 일을 잘 한다";
+    assert_eq!(
+        segments(input, &keep_all),
+        ["그 ", "사람은 ", "일을 ", "잘 ", "한다"]
+    );
+    assert_eq!(segments(input, &normal).len(), 9);
+
+    // Keeping words together doesn't apply to `Strictness::Anywhere`.
+    let anywhere = keep_all.clone().line_break(Strictness::Anywhere);
+    assert_eq!(segments("한다", &anywhere), ["한", "다"]);
+    // But it does with the other strictness modes.
+    let loose = keep_all.line_break(Strictness::Loose);
+    assert_eq!(segments("ケーキ", &loose), ["ケーキ"]);
+}
+
+#[test]
+fn ambiguous_characters() {
+    assert_eq!(original_break_class('①'), Class::AI);
+    assert_eq!(convert_to_break_class('①'), Class::AL);
+    assert_eq!(original_break_class('a'), Class::AL);
+
+    assert_eq!(
+        EastAsianContext::from_locale("zh_Hans_CN"),
+        EastAsianContext::EastAsian
+    );
+    assert_eq!(
+        EastAsianContext::from_locale("KO"),
+        EastAsianContext::EastAsian
+    );
+    assert_eq!(EastAsianContext::from_locale("jv"), EastAsianContext::Other);
+    assert_eq!(EastAsianContext::from_locale(""), EastAsianContext::Other);
+
+    let input = "a①b";
+    assert_eq!(
+        char_line_breaks_tailored(input, EastAsianContext::EastAsian),
+        [
+            (1, Break::Opportunity),
+            (2, Break::Opportunity),
+            (3, Break::Opportunity)
+        ]
+    );
+    assert_eq!(
+        char_line_breaks_tailored(input, EastAsianContext::Other),
+        char_line_breaks(input)
+    );
+    let css = CssOptions::new().east_asian(EastAsianContext::EastAsian);
+    assert_eq!(
+        char_line_breaks_tailored(input, &css),
+        char_line_breaks_tailored(input, EastAsianContext::EastAsian)
+    );
+    // Unresolved, `AI` acts as `AL`.
+    let unresolved = ClassOverrides::new().set('①', Class::AI);
+    assert_eq!(
+        char_line_breaks_tailored(input, &unresolved),
+        char_line_breaks(input)
+    );
+}
+
+#[test]
+fn line_break_property_values() {
+    let cases = [
+        ('a', LineBreakProperty::AL, Class::AL),
+        ('\u{E01}', LineBreakProperty::SA, Class::AL),
+        ('\u{E31}', LineBreakProperty::SA, Class::CM),
+        ('ぁ', LineBreakProperty::CJ, Class::NS),
+        ('①', LineBreakProperty::AI, Class::AL),
+        ('\u{378}', LineBreakProperty::XX, Class::AL),
+        ('\u{1FFFD}', LineBreakProperty::ID, Class::ID),
+        ('\u{20CF}', LineBreakProperty::PR, Class::PR),
+        ('\u{2028}', LineBreakProperty::BK, Class::BK),
+    ];
+    for &(c, property, class) in cases.iter() {
+        assert_eq!(line_break_property(c), property, "{:?}", c);
+        assert_eq!(convert_to_break_class(c), class, "{:?}", c);
+    }
+}
+
+#[test]
+fn ascii_fast_path() {
+    // Without `ascii_classes`, every `char` goes through `class`.
+    struct General;
+    impl Tailoring for General {}
+    let inputs = [
+        "The quick (\"brown\") fox can't jump 32.3 feet, right?\r\nNo.",
+        "Prices: $(1,234.56) and $(12) or $( 12) - 50% off!",
+        "ASCII, then 木禾夫 and \u{E01}\u{E31}, then ASCII $(1).",
+        "\t\x0B\x0C\x1F\x7F ~|{}[]",
+    ];
+    for input in inputs.iter() {
+        assert_eq!(
+            byte_line_breaks(input),
+            byte_line_breaks_tailored(input, General),
+            "{:?}",
+            input
+        );
+    }
+
+    let breaks = |tailoring| {
+        BreakOpportunities::with_tailoring("ab cd", tailoring)
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(breaks(CssOptions::new()), [3, 5]);
+    assert_eq!(
+        breaks(CssOptions::new().word_break(WordBreak::BreakAll)),
+        [1, 3, 4, 5]
+    );
+}
+
+#[test]
+fn break_iterator() {
+    fn check<T: Tailoring + Copy>(input: &str, tailoring: T) {
+        let mut boundaries = vec![0];
+        boundaries.extend(
+            byte_line_breaks_tailored(input, tailoring)
+                .into_iter()
+                .map(|(i, _)| i),
+        );
+        let breaks = BreakIterator::with_tailoring(input, tailoring);
+        assert_eq!(breaks.first(), 0);
+        assert_eq!(breaks.last(), input.len());
+        for offset in 0..input.len() + 2 {
+            let following = boundaries.iter().cloned().find(|&i| i > offset);
+            let preceding = boundaries.iter().cloned().rev().find(|&i| i < offset);
+            let is_boundary = boundaries.contains(&offset);
+            assert_eq!(
+                breaks.following(offset),
+                following,
+                "{:?} {}",
+                input,
+                offset
+            );
+            assert_eq!(
+                breaks.preceding(offset),
+                preceding,
+                "{:?} {}",
+                input,
+                offset
+            );
+            assert_eq!(
+                breaks.is_boundary(offset),
+                is_boundary,
+                "{:?} {}",
+                input,
+                offset
+            );
+        }
+    }
+
+    let inputs = [
+        "",
+        "a",
+        "Which is tree?\r\n木禾夫🤔\n\nend",
+        "a  b (  [x \"  y\" ) ,z",
+        "\u{200B}  a\u{2014}  \u{2014}b",
+        "🇸🇪🇫🇮🇸🇪 🇸🇪🇫🇮🇸 x",
+        "$(12.5) (12,-) 12/ ($) $(a) e\u{301}\u{301} a\u{200D}\u{301}",
+        "\u{5D0}-\u{5D1} \u{5D0}\u{2010}x 👍🏽👩\u{200D}👩",
+        "ฉันกินข้าว (ภาษาไทย) ภาษาไทยง่ายนิดเดียว",
+        "그 사람은 일을 잘 한다!",
+    ];
+    let thai = DictionarySegmenter::thai();
+    let keep_all = CssOptions::new().word_break(WordBreak::KeepAll);
+    let anywhere = CssOptions::new().line_break(Strictness::Anywhere);
+    for input in inputs.iter() {
+        check(input, NoTailoring);
+        check(input, &thai);
+        check(input, &keep_all);
+        check(input, &anywhere);
+    }
+}
+
+#[test]
+fn chunk_breaks() {
+    fn breaks<T: Tailoring + Copy>(chunks: &[&str], tailoring: T) -> Vec<(usize, Break)> {
+        let mut state = BreakerState::new();
+        let mut out = Vec::new();
+        for chunk in chunks {
+            let offset = state.offset();
+            let mut breaks = ChunkBreaks::with_tailoring(chunk, state, tailoring);
+            out.extend(&mut breaks);
+            state = breaks.state();
+            assert_eq!(state.offset(), offset + chunk.len());
+        }
+        out.extend(ChunkBreaks::with_tailoring("", state, tailoring).end_of_text());
+        out
+    }
+
+    let inputs = [
+        "",
+        "Which is tree?\r\n木禾夫🤔",
+        "$(12) $(a) $(\u{301}\u{301}12) 12$( \u{5D0}-\u{5D1} 🇸🇪🇫🇮🇸",
+        "a  b (  [x \"  y\" ) ,z $(",
+    ];
+    let keep_all = CssOptions::new().word_break(WordBreak::KeepAll);
+    for input in inputs.iter() {
+        let expected = byte_line_breaks(input);
+        let ends = input
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(Some(input.len()))
+            .collect::<Vec<_>>();
+        for &i in &ends {
+            for &j in ends.iter().filter(|&&j| j >= i) {
+                let chunks = [&input[..i], &input[i..j], &input[j..]];
+                assert_eq!(breaks(&chunks, NoTailoring), expected, "{:?}", chunks);
+            }
+        }
+        assert_eq!(
+            breaks(&[input], &keep_all),
+            byte_line_breaks_tailored(input, &keep_all)
+        );
+    }
+
+    // The chunk marked as the end of the text doesn't have to be empty.
+    let mut breaks = ChunkBreaks::new("a b", BreakerState::new());
+    let mut out = breaks.next().into_iter().collect::<Vec<_>>();
+    let state = breaks.state();
+    assert_eq!(state.offset(), 3);
+    out.extend(ChunkBreaks::new(" $(", state).end_of_text());
+    assert_eq!(out, byte_line_breaks("a b $("));
+}
+
+#[test]
+fn read_breaks() {
+    let input = "Which is (tree?)\r\n$(12) 木禾夫🤔 ภาษาไทยง่ายนิดเดียว ฉันกินข้าว";
+    for &capacity in [1, 2, 3, 7, 64].iter() {
+        let reader = BufReader::with_capacity(capacity, input.as_bytes());
+        let breaks = ReadBreaks::new(reader).collect::<io::Result<Vec<_>>>();
+        assert_eq!(breaks.unwrap(), byte_line_breaks(input));
+
+        let thai = DictionarySegmenter::thai();
+        let reader = BufReader::with_capacity(capacity, input.as_bytes());
+        let breaks = ReadBreaks::with_tailoring(reader, &thai).collect::<io::Result<Vec<_>>>();
+        assert_eq!(breaks.unwrap(), byte_line_breaks_tailored(input, &thai));
+    }
+    assert_eq!(ReadBreaks::new(&b""[..]).count(), 0);
+
+    let mut breaks = ReadBreaks::new(&b"a b\xE6\x9C"[..]);
+    assert_eq!(breaks.next().unwrap().unwrap(), (2, Break::Opportunity));
+    let error = breaks.next().unwrap().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(breaks.next().is_none());
+}
+
+#[test]
+fn code_units() {
+    let inputs = [
+        "",
+        "Which is tree?\r\n木禾夫🤔 👍🏽 🇸🇪🇫🇮🇸",
+        "$(12) $(\u{301}12) \u{5D0}-\u{5D1} 𝐀𝐁 (𝟏𝟐)",
+        "ภาษาไทยง่ายนิดเดียว 𝐀ฉันกินข้าว",
+    ];
+    let thai = DictionarySegmenter::thai();
+    for input in inputs.iter() {
+        let utf16 = input.encode_utf16().collect::<Vec<_>>();
+        let chars = input.chars().collect::<Vec<_>>();
+        let utf32 = input.chars().map(|c| c as u32).collect::<Vec<_>>();
+        let to_utf16 = |breaks: Vec<(usize, Break)>| {
+            breaks
+                .into_iter()
+                .map(|(i, br)| (input[..i].encode_utf16().count(), br))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            Utf16Breaks::new(&utf16).collect::<Vec<_>>(),
+            to_utf16(byte_line_breaks(input))
+        );
+        assert_eq!(
+            Utf16Breaks::with_tailoring(&utf16, &thai).collect::<Vec<_>>(),
+            to_utf16(byte_line_breaks_tailored(input, &thai))
+        );
+        assert_eq!(
+            Utf32Breaks::new(&chars).collect::<Vec<_>>(),
+            char_line_breaks(input)
+        );
+        assert_eq!(
+            Utf32Breaks::with_tailoring(&utf32, &thai).collect::<Vec<_>>(),
+            char_line_breaks_tailored(input, &thai)
+        );
+    }
+}
+
+#[test]
+fn lone_surrogates() {
+    // Treated as U+FFFD, whether paired the wrong way round or alone.
+    let replaced = char_line_breaks("a\u{FFFD}\u{FFFD} \u{FFFD}b\u{FFFD}");
+    let utf16 = [0x61, 0xDC00, 0xD800, 0x20, 0xD83C, 0x62, 0xD800];
+    assert_eq!(Utf16Breaks::new(&utf16).collect::<Vec<_>>(), replaced);
+    let utf32: [u32; 7] = [0x61, 0xDC00, 0xD800, 0x20, 0x110000, 0x62, 0xFFFF_FFFF];
+    assert_eq!(Utf32Breaks::new(&utf32).collect::<Vec<_>>(), replaced);
+
+    // Which is `AL` by default, but can be tailored.
+    assert_eq!(
+        Utf16Breaks::new(&[0x61, 0xD800]).collect::<Vec<_>>(),
+        [(2, Break::Opportunity)]
+    );
+    let breaks = Utf16Breaks::with_tailoring(&[0x61, 0xD800], EastAsianContext::EastAsian);
+    assert_eq!(
+        breaks.collect::<Vec<_>>(),
+        [(1, Break::Opportunity), (2, Break::Opportunity)]
+    );
+}
+
+#[test]
+fn incremental_breaks() {
+    fn check<T: Tailoring + Copy>(tailoring: T) {
+        let pieces = [
+            "",
+            " ",
+            "a",
+            "12",
+            "$(",
+            "(",
+            ")",
+            "\u{301}",
+            "\u{200D}",
+            "\r\n",
+            "木禾",
+            "🇸🇪",
+            "ภาษาไทย",
+            "ง่าย",
+            "\u{5D0}-",
+            "\"  ",
+            "\u{200B}",
+            "!",
+        ];
+        let mut text = String::from("Which is tree? $(12) ภาษาไทยง่ายนิดเดียว 木禾夫🤔\n");
+        let mut breaks = IncrementalBreaks::with_tailoring(&text, tailoring);
+        assert_eq!(
+            breaks.breaks(),
+            &byte_line_breaks_tailored(&text, tailoring)[..]
+        );
+        // A small linear congruential generator, to pick the edits.
+        let mut seed = 12345u32;
+        let mut random = |n: usize| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) as usize % n
+        };
+        for _ in 0..500 {
+            let boundaries = text
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain(Some(text.len()))
+                .collect::<Vec<_>>();
+            let start = boundaries[random(boundaries.len())];
+            let end = boundaries[random(boundaries.len())].max(start);
+            let end = if random(2) == 0 { start } else { end };
+            let replacement = pieces[random(pieces.len())];
+
+            let old = breaks.breaks().to_vec();
+            let changes = breaks.edit(start..end, replacement);
+            text.replace_range(start..end, replacement);
+            let expected = byte_line_breaks_tailored(&text, tailoring);
+            assert_eq!(breaks.text(), text);
+            assert_eq!(breaks.breaks(), &expected[..], "{:?}", text);
+
+            // The changes turn the old breaks into the new ones.
+            let shift = |i: usize| {
+                if i <= start {
+                    i
+                } else {
+                    i + start + replacement.len() - end
+                }
+            };
+            let mut changed = old
+                .iter()
+                .filter(|br| !changes.removed.contains(br))
+                .map(|&(i, br)| (shift(i), br))
+                .chain(changes.added.iter().cloned())
+                .collect::<Vec<_>>();
+            changed.sort_by_key(|&(i, _)| i);
+            assert_eq!(changed, expected, "{:?}", text);
+            assert!(changes
+                .added
+                .iter()
+                .all(|&(i, _)| changes.range.contains(&i) || i == text.len()));
+        }
+    }
+
+    check(NoTailoring);
+    check(&DictionarySegmenter::thai());
+    check(&CssOptions::new().line_break(Strictness::Anywhere));
+
+    // Only the text around the edit is broken again.
+    let text = "The quick (\"brown\") fox can't jump 32.3 feet, right?\n".repeat(100);
+    let mut breaks = IncrementalBreaks::new(&text);
+    assert_eq!(&text[534..539], "quick");
+    let changes = breaks.edit(534..539, "slow");
+    assert_eq!(changes.range, 534..539);
+    assert_eq!(breaks.breaks(), &byte_line_breaks(breaks.text())[..]);
+}
+
+#[test]
+fn explain() {
+    assert_eq!(explain_line_breaks(""), []);
+
+    let explanations = explain_line_breaks("\r\n$(1) 木");
+    let rules = explanations
+        .iter()
+        .map(|explanation| (explanation.char_index, explanation.br, explanation.rule))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rules,
+        [
+            (0, Break::Prohibited, Some(Rule::LB2)),
+            (1, Break::Prohibited, Some(Rule::LB5)),
+            (2, Break::Mandatory, Some(Rule::LB5)),
+            (3, Break::Prohibited, Some(Rule::LB25)),
+            (4, Break::Prohibited, Some(Rule::LB14)),
+            (5, Break::Prohibited, Some(Rule::LB13)),
+            (6, Break::Prohibited, Some(Rule::LB7)),
+            (7, Break::Opportunity, Some(Rule::LB18)),
+            (8, Break::Opportunity, Some(Rule::LB3))
+        ]
+    );
+    let last = explanations[explanations.len() - 1];
+    assert_eq!(
+        (last.byte_index, last.before, last.after),
+        (10, Some(Class::ID), None)
+    );
+
+    // LB25 only holds before an `OP` that is followed by a number.
+    assert_eq!(explain_line_breaks("$(a")[1].rule, Some(Rule::LB31));
+
+    let thai = DictionarySegmenter::thai();
+    let explanations = explain_line_breaks_tailored("ฉันกินข้าว", &thai);
+    assert_eq!(explanations[3].byte_index, 9);
+    assert_eq!(explanations[3].br, Break::Opportunity);
+    assert_eq!(explanations[3].rule, Some(Rule::LB1));
+    assert_eq!(explanations[4].rule, Some(Rule::LB9));
+
+    let table = RuleSetBuilder::new()
+        .disable(Rule::LB5)
+        .break_between(Class::ID, Class::ID, Break::Mandatory)
+        .build();
+    let explanations = explain_line_breaks_tailored("\r\n木禾", &table);
+    assert_eq!(explanations[1].rule, Some(Rule::LB6));
+    assert_eq!(explanations[3].br, Break::Mandatory);
+    assert_eq!(explanations[3].rule, None);
+
+    // The breaks are the same as those of `byte_line_breaks`.
+    let inputs = [
+        "Price: $(1,234.56)\nDone",
+        "ฉันกินข้าว (ภาษาไทย)",
+        "a\u{308}\u{200D}b 🇯🇵🇫🇷🇩🇪 — “木禾” 1-2",
+    ];
+    for input in inputs.iter() {
+        let breaks = explain_line_breaks_tailored(input, &thai)
+            .into_iter()
+            .filter(|explanation| explanation.br != Break::Prohibited)
+            .map(|explanation| (explanation.byte_index, explanation.br))
+            .collect::<Vec<_>>();
+        assert_eq!(breaks, byte_line_breaks_tailored(input, &thai));
+    }
+}
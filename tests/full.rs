@@ -2,29 +2,29 @@ extern crate regex;
 extern crate uax_14;
 use regex::Regex;
 use std::char;
-use uax_14::{char_line_breaks, convert_to_break_class, Break, Class};
+use uax_14::{
+    char_line_breaks, char_line_breaks_tailored, convert_to_break_class, explain_line_breaks,
+    Break, Class, RuleSetBuilder, Utf32Breaks, UNICODE_VERSION,
+};
 
-// LB25 Disagrees with these tests
-const SKIP_TESTS: [usize; 30] = [
-    1113, 1115, 1117, 1119, 1281, 1283, 1285, 1287, 2953, 2955, 4469, 4471, 4637, 4639, 5137, 5139,
-    7109, 7118, 7123, 7208, 7209, 7210, 7211, 7212, 7213, 7215, 7216, 7217, 7218, 7219,
-];
-
-const DATA: &'static str = include_str!("data.txt");
+// The conformance tests of the version of Unicode selected by the features.
+fn test_data() -> &'static str {
+    match UNICODE_VERSION {
+        (11, 0, _) => include_str!("../unicode-data/LineBreakTest-11.0.0.txt"),
+        version => panic!("There is no LineBreakTest.txt for Unicode {:?}", version),
+    }
+}
 
 fn main() {
+    let data = test_data();
     let re1 = Regex::new(r"×(( [0-9A-F]+ [÷×])+)").unwrap();
     let re2 = Regex::new(r"([0-9A-F]+) ([÷×])").unwrap();
     let mut correct = 0;
     let mut total = 0;
     let mut printing = true;
-    for (i, caps) in re1.captures_iter(DATA).enumerate() {
-        if SKIP_TESTS.contains(&(i + 1)) {
-            if printing {
-                print!(".");
-            }
-            continue;
-        }
+    // Built at runtime, it should behave exactly like the built-in table
+    let table = RuleSetBuilder::new().build();
+    for (i, caps) in re1.captures_iter(data).enumerate() {
         total += 1;
 
         let parts = caps.get(1).unwrap().as_str();
@@ -49,8 +49,16 @@ fn main() {
             .iter()
             .map(|i| char::from_u32(*i).unwrap())
             .collect();
-        let my_answer: Vec<usize> = char_line_breaks(&input_string).iter().map(|x| x.0).collect();
-        if my_answer == indices {
+        let my_answer: Vec<usize> = char_line_breaks(&input_string)
+            .iter()
+            .map(|x| x.0)
+            .collect();
+        let table_answer: Vec<usize> = char_line_breaks_tailored(&input_string, &table)
+            .iter()
+            .map(|x| x.0)
+            .collect();
+        let utf32_answer: Vec<usize> = Utf32Breaks::new(&just_codepoints).map(|x| x.0).collect();
+        if my_answer == indices && table_answer == indices && utf32_answer == indices {
             correct += 1;
             if printing {
                 print!("i");
@@ -72,5 +80,65 @@ fn main() {
             printing = false;
         }
     }
-    println!("\n{}/{} ({} ignored)", correct, total, SKIP_TESTS.len());
+    println!("\n{}/{}", correct, total);
+    assert_eq!(correct, total);
+
+    check_rules(data);
+}
+
+// The name of the rule given by the comment of a test for a position, like
+// `[25.03]` for LB25. A first decimal of 1 or 2 stands for the rules with a
+// letter, like `[21.1]` for LB21a, except for LB12a which is `[12.1]` to
+// `[12.3]`.
+fn expected_rule(number: &str, start: bool) -> String {
+    let mut parts = number.split('.');
+    let (major, minor) = (parts.next().unwrap(), parts.next().unwrap());
+    match major {
+        "0" if start => "LB2".to_string(),
+        "0" => "LB3".to_string(),
+        "999" => "LB31".to_string(),
+        "12" if minor != "0" => "LB12a".to_string(),
+        _ => {
+            let letter = match minor.as_bytes()[0] {
+                b'1' => "a",
+                b'2' => "b",
+                _ => "",
+            };
+            format!("LB{}{}", major, letter)
+        }
+    }
+}
+
+fn check_rules(data: &str) {
+    let re = Regex::new(r"(?m)^×([^#]*)#(.*)$").unwrap();
+    let number = Regex::new(r"\[([0-9.]+)\]").unwrap();
+    let mut correct = 0;
+    let mut total = 0;
+    for caps in re.captures_iter(data) {
+        let input: String = caps[1]
+            .split(['×', '÷'])
+            .filter_map(|part| u32::from_str_radix(part.trim(), 16).ok())
+            .map(|n| char::from_u32(n).unwrap())
+            .collect();
+        let explanations = explain_line_breaks(&input);
+        let numbers = number.captures_iter(&caps[2]).collect::<Vec<_>>();
+        assert_eq!(explanations.len(), numbers.len());
+        for (explanation, number) in explanations.iter().zip(numbers.iter()) {
+            total += 1;
+            let expected = expected_rule(&number[1], explanation.byte_index == 0);
+            let rule = format!("{:?}", explanation.rule.unwrap());
+            // The test data follows the tailoring of LB13 from the examples of
+            // the annex, which leaves `NU × CL` and the like to LB25.
+            if rule == expected || (expected == "LB25" && rule == "LB13") {
+                correct += 1;
+            } else {
+                println!(
+                    "Rule of {:?} at {}: {} instead of {}",
+                    input, explanation.byte_index, rule, expected
+                );
+            }
+        }
+    }
+    println!("Rules: {}/{}", correct, total);
+    assert_eq!(correct, total);
 }
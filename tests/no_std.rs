@@ -0,0 +1,50 @@
+//! Checks that the crate builds for an embedded target without `std`, both
+//! with and without `alloc`.
+//!
+//! The target has to be installed, which is done with
+//! `rustup target add thumbv7em-none-eabihf`.
+use std::path::Path;
+use std::process::Command;
+
+const TARGET: &str = "thumbv7em-none-eabihf";
+
+fn target_installed() -> bool {
+    let output = Command::new("rustc")
+        .args(["--print", "target-libdir", "--target", TARGET])
+        .output()
+        .expect("Could not run rustc");
+    let libdir = String::from_utf8_lossy(&output.stdout);
+    output.status.success() && Path::new(libdir.trim()).exists()
+}
+
+fn build(features: &[&str]) {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("no_std");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args([
+            "build",
+            "--lib",
+            "--no-default-features",
+            "--target",
+            TARGET,
+        ])
+        .arg("--features")
+        .arg(features.join(","))
+        .arg("--target-dir")
+        .arg(target_dir)
+        .status()
+        .expect("Could not run cargo");
+    assert!(status.success(), "Build failed with {:?}", features);
+}
+
+#[test]
+fn thumbv7em() {
+    assert!(
+        target_installed(),
+        "The target {} isn't installed, add it with `rustup target add {}`",
+        TARGET,
+        TARGET
+    );
+    build(&[]);
+    build(&["alloc"]);
+}
@@ -0,0 +1,130 @@
+#[allow(dead_code)]
+#[path = "../build/ucd.rs"]
+mod ucd;
+
+use ucd::{Error, Property, UcdFile};
+
+fn error(line: usize, message: &str) -> Error {
+    Error {
+        file: "Test.txt".to_string(),
+        line,
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn properties() {
+    let file = UcdFile::new(
+        "Test.txt",
+        "# A comment\n\
+         # @missing: 0000..10FFFF; XX\n\
+         \n\
+         0000..0008;CM     # Cc     [9] <control-0000>..<control-0008>\n\
+         000A ; LF\n\
+         10FFFF;Some_Value;with more fields\n",
+    );
+    assert_eq!(
+        file.properties(),
+        Ok(vec![
+            Property {
+                line: 4,
+                first: 0x0,
+                last: 0x8,
+                value: "CM",
+            },
+            Property {
+                line: 5,
+                first: 0xA,
+                last: 0xA,
+                value: "LF",
+            },
+            Property {
+                line: 6,
+                first: 0x10FFFF,
+                last: 0x10FFFF,
+                value: "Some_Value",
+            },
+        ])
+    );
+}
+
+#[test]
+fn property_errors() {
+    let cases = [
+        ("0041;AL\n0042\n", error(2, "Expected 2 fields, found 1")),
+        (
+            "0041;AL\n\n00G1;AL\n",
+            error(3, "Invalid code point `00G1`"),
+        ),
+        ("+041;AL\n", error(1, "Invalid code point `+041`")),
+        ("110000;AL\n", error(1, "Invalid code point `110000`")),
+        ("0042..0041;AL\n", error(1, "Empty range `0042..0041`")),
+        ("0041..;AL\n", error(1, "Invalid code point ``")),
+        ("0041;A L\n", error(1, "Invalid value `A L`")),
+        ("0041; # AL\n", error(1, "Invalid value ``")),
+    ];
+    for &(contents, ref expected) in cases.iter() {
+        let file = UcdFile::new("Test.txt", contents);
+        assert_eq!(file.properties().as_ref(), Err(expected), "{:?}", contents);
+    }
+    let file = UcdFile::new("Test.txt", "0041;AL\n00G1;AL\n");
+    assert_eq!(
+        file.properties().unwrap_err().to_string(),
+        "Test.txt:2: Invalid code point `00G1`"
+    );
+}
+
+#[test]
+fn general_categories() {
+    let file = UcdFile::new(
+        "Test.txt",
+        "0300;COMBINING GRAVE ACCENT;Mn;230;NSM;;;;;N;NON-SPACING GRAVE;;;;\n\
+         4E00;<CJK Ideograph, First>;Lo;0;L;;;;;N;;;;;\n\
+         9FEF;<CJK Ideograph, Last>;Lo;0;L;;;;;N;;;;;\n",
+    );
+    assert_eq!(
+        file.general_categories(),
+        Ok(vec![
+            Property {
+                line: 1,
+                first: 0x300,
+                last: 0x300,
+                value: "Mn",
+            },
+            Property {
+                line: 2,
+                first: 0x4E00,
+                last: 0x9FEF,
+                value: "Lo",
+            },
+        ])
+    );
+
+    let unended = UcdFile::new(
+        "Test.txt",
+        "4E00;<CJK Ideograph, First>;Lo;0;L;;;;;N;;;;;\n",
+    );
+    assert_eq!(
+        unended.general_categories(),
+        Err(error(1, "The range is never ended"))
+    );
+    let interrupted = UcdFile::new(
+        "Test.txt",
+        "4E00;<CJK Ideograph, First>;Lo;0;L;;;;;N;;;;;\n\
+         4E01;CJK IDEOGRAPH-4E01;Lo;0;L;;;;;N;;;;;\n",
+    );
+    assert_eq!(
+        interrupted.general_categories(),
+        Err(error(2, "Expected the end of the range started on line 1"))
+    );
+}
+
+#[test]
+fn missing_file() {
+    let err = UcdFile::read("unicode-data/Missing.txt".as_ref()).unwrap_err();
+    assert_eq!(err.file, "unicode-data/Missing.txt");
+    assert_eq!(err.line, 0);
+    assert!(err
+        .to_string()
+        .starts_with("unicode-data/Missing.txt: Could not read the file"));
+}